@@ -11,5 +11,8 @@ pub const EXEC_SELF_GAS: Gas = Gas(20 * Gas::ONE_TERA.0);
 pub const MAX_EXEC_FUN_CALL_GAS: Gas =
     Gas(300 * Gas::ONE_TERA.0 - EXEC_SELF_GAS.0 - EXECUTE_CALLBACK_GAS.0);
 
+/// Maximum number of entries kept in the `members_history` ring buffer.
+pub const MEMBERS_HISTORY_LEN: usize = 20;
+
 // 64bytes(accountID) + 1byte (prefix) + 4bytes(proposal_id) + vote(byte) = 72B -> add 20% margin = < 90B
 pub const VOTE_STORAGE: u64 = 90;
@@ -2,6 +2,8 @@ use near_sdk::env::panic_str;
 use near_sdk::serde::Serialize;
 use near_sdk::FunctionError;
 
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
 #[cfg_attr(not(target_arch = "wasm32"), derive(PartialEq, Debug))]
 pub enum VoteError {
     NotAuthorized,
@@ -9,6 +11,10 @@ pub enum VoteError {
     NotInProgress,
     NotActive,
     NoSelfVote,
+    /// The DAO term is over.
+    TermOver,
+    Dissolved,
+    PropNotFound,
 }
 
 impl FunctionError for VoteError {
@@ -19,6 +25,9 @@ impl FunctionError for VoteError {
             VoteError::NotInProgress => panic_str("proposal not in progress"),
             VoteError::NotActive => panic_str("voting time is over"),
             VoteError::NoSelfVote => panic_str("not allowed to vote on proposal against them"),
+            VoteError::TermOver => panic_str("dao term is over, call dissolve_hook!"),
+            VoteError::Dissolved => panic_str("dao is dissolved"),
+            VoteError::PropNotFound => panic_str("proposal does not exist"),
         }
     }
 }
@@ -53,6 +62,8 @@ pub enum CreatePropError {
     KindNotAllowed,
     Storage(String),
     Gas(String),
+    Frozen,
+    BadRequest(String),
 }
 
 impl FunctionError for CreatePropError {
@@ -63,6 +74,27 @@ impl FunctionError for CreatePropError {
             CreatePropError::KindNotAllowed => panic_str("proposal kind not allowed"),
             CreatePropError::Storage(reason) => panic_str(reason),
             CreatePropError::Gas(reason) => panic_str(reason),
+            CreatePropError::Frozen => panic_str("proposal creation is frozen"),
+            CreatePropError::BadRequest(reason) => panic_str(reason),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(PartialEq, Debug))]
+pub enum CancelPropError {
+    NotAuthorized,
+    NotInProgress,
+    HasVotes,
+}
+
+impl FunctionError for CancelPropError {
+    fn panic(&self) -> ! {
+        match self {
+            CancelPropError::NotAuthorized => panic_str("only the proposer can cancel a proposal"),
+            CancelPropError::NotInProgress => panic_str("proposal not in progress"),
+            CancelPropError::HasVotes => panic_str("can't cancel a proposal with votes"),
         }
     }
 }
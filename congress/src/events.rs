@@ -1,4 +1,4 @@
-use near_sdk::{serde::Serialize, AccountId};
+use near_sdk::{json_types::U128, serde::Serialize, AccountId, Balance};
 use serde_json::json;
 
 use crate::{proposal::PropKind, ExecError};
@@ -42,6 +42,23 @@ pub(crate) fn emit_executed(prop_id: u32) {
     });
 }
 
+/// * `overflow`: amount by which the budget cap would be exceeded.
+pub(crate) fn emit_budget_exceeded(prop_id: u32, overflow: Balance) {
+    emit_event(EventPayload {
+        event: "budget-exceeded",
+        data: json!({ "prop_id": prop_id, "overflow": U128(overflow) }),
+    });
+}
+
+/// Emitted when `execute` fails.
+/// * `reason`: the `ExecError`, eg "ExecTime" or "MinVoteDuration".
+pub(crate) fn emit_execute_rejected(prop_id: u32, reason: &ExecError) {
+    emit_event(EventPayload {
+        event: "execute-rejected",
+        data: json!({ "prop_id": prop_id, "reason": reason }),
+    });
+}
+
 pub(crate) fn emit_veto(prop_id: u32) {
     emit_event(EventPayload {
         event: "veto",
@@ -49,6 +66,13 @@ pub(crate) fn emit_veto(prop_id: u32) {
     });
 }
 
+pub(crate) fn emit_cancel(prop_id: u32) {
+    emit_event(EventPayload {
+        event: "cancel",
+        data: json!({ "prop_id": prop_id }),
+    });
+}
+
 pub(crate) fn emit_dissolve() {
     emit_event(EventPayload {
         event: "dissolve",
@@ -56,9 +80,30 @@ pub(crate) fn emit_dissolve() {
     });
 }
 
+pub(crate) fn emit_freeze() {
+    emit_event(EventPayload {
+        event: "freeze",
+        data: "",
+    });
+}
+
+pub(crate) fn emit_unfreeze() {
+    emit_event(EventPayload {
+        event: "unfreeze",
+        data: "",
+    });
+}
+
 pub(crate) fn emit_dismiss(member: &AccountId) {
     emit_event(EventPayload {
         event: "dismiss",
         data: json!({ "member": member }),
     });
 }
+
+pub(crate) fn emit_members_update(members_len: u8) {
+    emit_event(EventPayload {
+        event: "members-update",
+        data: json!({ "members_len": members_len }),
+    });
+}
@@ -5,7 +5,7 @@ use common::errors::HookError;
 use common::finalize_storage_check;
 use events::*;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap};
+use near_sdk::collections::{LazyOption, LookupMap, LookupSet};
 use near_sdk::json_types::{Base64VecU8, U128};
 use near_sdk::{
     env, near_bindgen, require, AccountId, Balance, Gas, PanicOnDefault, Promise, PromiseError,
@@ -37,6 +37,8 @@ pub struct Contract {
     pub registry: AccountId,
 
     pub dissolved: bool,
+    /// when frozen, new proposals can't be created. Voting and execution is still allowed.
+    pub frozen: bool,
     pub prop_counter: u32,
     pub proposals: LookupMap<u32, Proposal>,
 
@@ -48,6 +50,12 @@ pub struct Contract {
     pub members_len: u8,
     /// minimum amount of members to approve the proposal
     pub threshold: u8,
+    /// minimum amount of members who must vote (approve, reject or abstain) to approve the
+    /// proposal.
+    pub min_participation: u8,
+    /// Ring buffer of `(timestamp_ms, members_len)` recorded after each members change.
+    /// Keeps at most `MEMBERS_HISTORY_LEN` latest entries.
+    pub members_history: LazyOption<Vec<(u64, u8)>>,
 
     /// Map of accounts authorized to call hooks.
     pub hook_auth: LazyOption<HashMap<AccountId, Vec<HookPerm>>>,
@@ -63,12 +71,16 @@ pub struct Contract {
     pub budget_cap: Balance,
     /// size (in yocto NEAR) of the big funding request
     pub big_funding_threshold: Balance,
+    /// ids of proposals executed automatically by the deciding vote (when `cooldown == 0`).
+    pub auto_executed: LookupSet<u32>,
 }
 
 #[near_bindgen]
 impl Contract {
     #[init]
     /// * hook_auth : map of accounts authorized to call hooks
+    /// * min_participation: minimum amount of members who must vote to approve a proposal.
+    ///   Must not be bigger than the number of members.
     pub fn new(
         community_fund: AccountId,
         start_time: u64,
@@ -82,21 +94,29 @@ impl Contract {
         budget_cap: U128,
         big_funding_threshold: U128,
         registry: AccountId,
+        min_participation: u8,
     ) -> Self {
         // we can support up to 255 with the limitation of the proposal type, but setting 100
         // here because this is more than enough for what we need to test for Congress.
         let members_len = members.len() as u8;
         near_sdk::require!(members_len <= 100, "max amount of members is 100");
+        near_sdk::require!(
+            min_participation <= members_len,
+            "min_participation can't be bigger than the number of members"
+        );
         let threshold = (members_len / 2) + 1;
         members.sort();
         Self {
             community_fund,
             dissolved: false,
+            frozen: false,
             prop_counter: 0,
             proposals: LookupMap::new(StorageKey::Proposals),
             members: LazyOption::new(StorageKey::Members, Some(&(members, member_perms))),
             members_len,
             threshold,
+            min_participation,
+            members_history: LazyOption::new(StorageKey::MembersHistory, Some(&Vec::new())),
             hook_auth: LazyOption::new(StorageKey::HookAuth, Some(&hook_auth)),
             start_time,
             end_time,
@@ -107,6 +127,7 @@ impl Contract {
             budget_cap: budget_cap.0,
             big_funding_threshold: big_funding_threshold.0,
             registry,
+            auto_executed: LookupSet::new(StorageKey::AutoExecuted),
         }
     }
 
@@ -122,6 +143,8 @@ impl Contract {
     /// Returns the new proposal ID.
     /// Caller is required to attach enough deposit to cover the proposal storage as well as all
     /// possible votes (2*self.threshold - 1).
+    /// Fails with `BadRequest` if the proposal voting and cooldown would end after the DAO
+    /// term (`now + vote_duration + cooldown > end_time`).
     /// NOTE: storage is paid from the account state
     #[payable]
     #[handle_result]
@@ -131,6 +154,9 @@ impl Contract {
         description: String,
     ) -> Result<u32, CreatePropError> {
         self.assert_active();
+        if self.frozen {
+            return Err(CreatePropError::Frozen);
+        }
         let storage_start = env::storage_usage();
         let user = env::predecessor_account_id();
         let (members, perms) = self.members.get().unwrap();
@@ -151,6 +177,11 @@ impl Contract {
                 new_budget = self.budget_spent + b.0 * (self.remaining_months(now) as u128);
             }
             PropKind::FunctionCall { actions, .. } => {
+                if actions.is_empty() {
+                    return Err(CreatePropError::BadRequest(
+                        "function call proposal must have at least one action".to_owned(),
+                    ));
+                }
                 let mut sum_gas = 0;
                 for a in actions {
                     if a.gas.0 < EXEC_CTR_CALL_GAS.0 || a.gas.0 > MAX_EXEC_FUN_CALL_GAS.0 {
@@ -166,11 +197,24 @@ impl Contract {
                     ));
                 }
             }
+            PropKind::UpdateMemberPerms(p) => {
+                if !p.contains(&PropPerm::Text) {
+                    return Err(CreatePropError::BadRequest(
+                        "member permissions must include Text".to_owned(),
+                    ));
+                }
+            }
             _ => (),
         };
         if new_budget > self.budget_cap {
             return Err(CreatePropError::BudgetOverflow);
         }
+        // proposal must be executable before the end of the term
+        if now + self.vote_duration + self.cooldown > self.end_time {
+            return Err(CreatePropError::BadRequest(
+                "proposal voting and cooldown would end after the DAO term".to_owned(),
+            ));
+        }
 
         self.prop_counter += 1;
         emit_prop_created(self.prop_counter, &kind);
@@ -201,13 +245,18 @@ impl Contract {
 
     #[handle_result]
     pub fn vote(&mut self, id: u32, vote: Vote) -> Result<(), VoteError> {
-        self.assert_active();
+        if self.dissolved {
+            return Err(VoteError::Dissolved);
+        }
+        if env::block_timestamp_ms() > self.end_time {
+            return Err(VoteError::TermOver);
+        }
         let user = env::predecessor_account_id();
         let (members, _) = self.members.get().unwrap();
         if members.binary_search(&user).is_err() {
             return Err(VoteError::NotAuthorized);
         }
-        let mut prop = self.assert_proposal(id);
+        let mut prop = self.proposals.get(&id).ok_or(VoteError::PropNotFound)?;
 
         self.assert_member_not_involved(&prop, &user)?;
 
@@ -219,13 +268,10 @@ impl Contract {
             return Err(VoteError::NotActive);
         }
 
-        prop.add_vote(user, vote, self.threshold)?;
-        prop.finalize_status(
-            members.len(),
-            self.threshold,
-            self.min_vote_duration,
-            self.vote_duration,
-        );
+        prop.add_vote(user, vote, self.threshold, self.min_participation)?;
+        if !prop.can_skip_finalize(members.len(), self.threshold, self.min_vote_duration) {
+            prop.status = self.compute_status(&prop, now);
+        }
 
         self.proposals.insert(&id, &prop);
         emit_vote(id);
@@ -233,38 +279,128 @@ impl Contract {
         // automatic execution
         if matches!(prop.status, ProposalStatus::Approved) && self.cooldown == 0 {
             // We ignore a failure of self.execute here to assure that the vote is counted.
-            let res = self.execute(id);
+            let res = self.execute_impl(id);
             if res.is_err() {
                 emit_vote_execute_fail(id, res.err().unwrap());
+            } else {
+                self.auto_executed.insert(&id);
             }
         }
 
         Ok(())
     }
 
+    /// Casts multiple votes in a single transaction. Each vote is processed as a separate
+    /// `vote` call (including the automatic execution), and the result of each vote is
+    /// returned in the same order as the input. A failed vote (including a vote for a not
+    /// existing proposal) doesn't revert the other ones.
+    pub fn vote_many(&mut self, votes: Vec<(u32, Vote)>) -> Vec<Result<(), VoteError>> {
+        votes
+            .into_iter()
+            .map(|(id, vote)| self.vote(id, vote))
+            .collect()
+    }
+
+    /// Allows the proposer to cancel an in progress proposal before any vote was cast.
+    /// The proposal is kept with the `Cancelled` status, and the storage deposit reserved for
+    /// the votes is returned to the proposer.
+    /// Emits `cancel` event.
+    #[handle_result]
+    pub fn cancel_proposal(&mut self, id: u32) -> Result<(), CancelPropError> {
+        self.assert_active();
+        let mut prop = self.assert_proposal(id);
+        if prop.proposer != env::predecessor_account_id() {
+            return Err(CancelPropError::NotAuthorized);
+        }
+        let (members, _) = self.members.get().unwrap();
+        prop.finalize_status(
+            members.len(),
+            self.threshold,
+            self.min_participation,
+            self.min_vote_duration,
+            self.vote_duration,
+        );
+        if !matches!(prop.status, ProposalStatus::InProgress) {
+            return Err(CancelPropError::NotInProgress);
+        }
+        if !prop.votes.is_empty() {
+            return Err(CancelPropError::HasVotes);
+        }
+
+        prop.status = ProposalStatus::Cancelled;
+        self.proposals.insert(&id, &prop);
+        emit_cancel(id);
+
+        // no more votes will be stored, so we can return the reserved vote storage
+        let refund =
+            (VOTE_STORAGE * (2 * self.threshold - 1) as u64) as u128 * env::storage_byte_cost();
+        Promise::new(prop.proposer).transfer(refund);
+        Ok(())
+    }
+
     /// Allows anyone to execute proposal.
     /// If `contract.cooldown` is set, then a proposal can be only executed after the cooldown:
     /// (submission_time + vote_duration + cooldown).
+    /// Emits `execute-rejected` event when the proposal can't be executed.
     #[handle_result]
     pub fn execute(
         &mut self,
         id: u32,
+    ) -> Result<PromiseOrValue<Result<(), ExecRespErr>>, ExecError> {
+        self.execute_impl(id).map_err(|err| {
+            emit_execute_rejected(id, &err);
+            err
+        })
+    }
+
+    /// Allows anyone to execute a proposal approved during the DAO term (stored `Approved`
+    /// status) after the term is over, when `execute` is blocked. Failed and in progress
+    /// proposals are rejected. The cooldown is still required.
+    /// Panics if the DAO is dissolved or the term is not over yet.
+    /// Emits `execute-rejected` event when the proposal can't be executed.
+    #[handle_result]
+    pub fn execute_after_term(
+        &mut self,
+        id: u32,
+    ) -> Result<PromiseOrValue<Result<(), ExecRespErr>>, ExecError> {
+        require!(!self.dissolved, "dao is dissolved");
+        require!(
+            env::block_timestamp_ms() > self.end_time,
+            "dao term is not over, use execute"
+        );
+        let res = match self.assert_proposal(id).status {
+            ProposalStatus::Approved => self.execute_prop(id),
+            ProposalStatus::Executed => Err(ExecError::AlreadyExecuted),
+            _ => Err(ExecError::NotApproved),
+        };
+        res.map_err(|err| {
+            emit_execute_rejected(id, &err);
+            err
+        })
+    }
+
+    fn execute_impl(
+        &mut self,
+        id: u32,
     ) -> Result<PromiseOrValue<Result<(), ExecRespErr>>, ExecError> {
         self.assert_active();
+        self.execute_prop(id)
+    }
+
+    fn execute_prop(
+        &mut self,
+        id: u32,
+    ) -> Result<PromiseOrValue<Result<(), ExecRespErr>>, ExecError> {
         let mut prop = self.assert_proposal(id);
         if matches!(prop.status, ProposalStatus::Executed) {
             // More fine-grained errors
             return Err(ExecError::AlreadyExecuted);
         }
+        let now = env::block_timestamp_ms();
         // check if we can finalize the proposal status due to having enough votes during min_vote_duration
         if matches!(prop.status, ProposalStatus::InProgress) {
-            let (members, _) = self.members.get().unwrap();
-            if !prop.finalize_status(
-                members.len(),
-                self.threshold,
-                self.min_vote_duration,
-                self.vote_duration,
-            ) {
+            prop.status = self.compute_status(&prop, now);
+            if !prop.past_min_vote_duration_at(self.min_vote_duration, now) {
                 return Err(ExecError::MinVoteDuration);
             }
         }
@@ -276,7 +412,6 @@ impl Contract {
             return Err(ExecError::NotApproved);
         }
 
-        let now = env::block_timestamp_ms();
         if self.cooldown > 0 && now <= prop.approved_at.unwrap() + self.cooldown {
             return Err(ExecError::ExecTime);
         }
@@ -305,6 +440,12 @@ impl Contract {
                 budget = b.0 * self.remaining_months(now) as u128
             }
             PropKind::Text => (),
+            PropKind::UpdateBigFundingThreshold(t) => self.big_funding_threshold = t.0,
+            PropKind::UpdateRegistry(r) => self.registry = r.clone(),
+            PropKind::UpdateMemberPerms(p) => {
+                let (members, _) = self.members.get().unwrap();
+                self.members.set(&(members, p.clone()));
+            }
             PropKind::DismissAndBan { member, house } => {
                 self.proposals.insert(&id, &prop);
 
@@ -342,6 +483,7 @@ impl Contract {
             if self.budget_spent > self.budget_cap {
                 prop.status = ProposalStatus::Rejected;
                 self.proposals.insert(&id, &prop);
+                emit_budget_exceeded(id, self.budget_spent - self.budget_cap);
                 return Ok(PromiseOrValue::Value(Err(ExecRespErr::BudgetOverflow)));
             }
         }
@@ -369,39 +511,8 @@ impl Contract {
     pub fn veto_hook(&mut self, id: u32) -> Result<(), HookError> {
         self.assert_active();
         let mut proposal = self.assert_proposal(id);
-        let is_big_or_recurrent = match proposal.kind {
-            PropKind::FundingRequest(b) => b.0 >= self.big_funding_threshold,
-            PropKind::RecurrentFundingRequest(_) => true,
-            _ => false,
-        };
-        let caller = env::predecessor_account_id();
-        if is_big_or_recurrent {
-            self.assert_hook_perm(
-                &caller,
-                &[HookPerm::VetoBigOrReccurentFundingReq, HookPerm::VetoAll],
-            )?;
-        } else {
-            self.assert_hook_perm(&caller, &[HookPerm::VetoAll])?;
-        }
-
-        match proposal.status {
-            ProposalStatus::InProgress => {
-                proposal.status = ProposalStatus::Vetoed;
-            }
-            ProposalStatus::Approved => {
-                let cooldown = min(
-                    proposal.submission_time + self.vote_duration,
-                    proposal.approved_at.unwrap(),
-                ) + self.cooldown;
-                if cooldown < env::block_timestamp_ms() {
-                    return Err(HookError::CooldownOver);
-                }
-                proposal.status = ProposalStatus::Vetoed;
-            }
-            _ => {
-                return Err(HookError::ProposalFinalized);
-            }
-        }
+        self.check_veto(&env::predecessor_account_id(), &proposal)?;
+        proposal.status = ProposalStatus::Vetoed;
         emit_veto(id);
         self.proposals.insert(&id, &proposal);
         Ok(())
@@ -415,7 +526,22 @@ impl Contract {
         if env::block_timestamp_ms() <= self.end_time {
             self.assert_hook_perm(&env::predecessor_account_id(), &[HookPerm::Dissolve])?;
         }
-        self.dissolve_and_cleanup();
+        self.dissolve_and_cleanup(self.community_fund.clone());
+        Ok(())
+    }
+
+    /// Emergency dissolve: dissolves and finalizes the DAO, sending the excess account funds
+    /// to the `recipient` instead of the community fund. Requires `HookPerm::Dissolve`, also
+    /// when the DAO term is over.
+    /// Panics if the `recipient` is this contract.
+    #[handle_result]
+    pub fn dissolve_hook_to(&mut self, recipient: AccountId) -> Result<(), HookError> {
+        self.assert_hook_perm(&env::predecessor_account_id(), &[HookPerm::Dissolve])?;
+        require!(
+            recipient != env::current_account_id(),
+            "recipient can't be the congress contract"
+        );
+        self.dissolve_and_cleanup(recipient);
         Ok(())
     }
 
@@ -433,19 +559,42 @@ impl Contract {
 
         emit_dismiss(&member);
 
-        // Update threshold and members_len
+        // Update threshold, members_len and min_participation
         self.members_len = members.len() as u8;
         self.threshold = (self.members_len / 2) + 1;
+        self.min_participation = min(self.min_participation, self.members_len);
+        self.record_members_len();
 
         // If DAO doesn't have required threshold, then we dissolve.
         if members.len() < 2 {
-            self.dissolve_and_cleanup();
+            self.dissolve_and_cleanup(self.community_fund.clone());
         }
 
         self.members.set(&(members, perms));
         Ok(())
     }
 
+    /// Freezes the proposal creation. Voting and execution of existing proposals is still
+    /// allowed.
+    #[handle_result]
+    pub fn freeze_hook(&mut self) -> Result<(), HookError> {
+        self.assert_active();
+        self.assert_hook_perm(&env::predecessor_account_id(), &[HookPerm::Freeze])?;
+        self.frozen = true;
+        emit_freeze();
+        Ok(())
+    }
+
+    /// Unfreezes the proposal creation.
+    #[handle_result]
+    pub fn unfreeze_hook(&mut self) -> Result<(), HookError> {
+        self.assert_active();
+        self.assert_hook_perm(&env::predecessor_account_id(), &[HookPerm::Freeze])?;
+        self.frozen = false;
+        emit_unfreeze();
+        Ok(())
+    }
+
     /*****************
      * INTERNAL
      ****************/
@@ -471,6 +620,53 @@ impl Contract {
         Err(HookError::NotAuthorized)
     }
 
+    /// Checks if `caller` has the veto permission for the proposal kind, and if the proposal
+    /// can still be vetoed.
+    fn check_veto(&self, caller: &AccountId, proposal: &Proposal) -> Result<(), HookError> {
+        let is_big_or_recurrent = match proposal.kind {
+            PropKind::FundingRequest(b) => b.0 >= self.big_funding_threshold,
+            PropKind::RecurrentFundingRequest(_) => true,
+            _ => false,
+        };
+        if is_big_or_recurrent {
+            self.assert_hook_perm(
+                caller,
+                &[HookPerm::VetoBigOrReccurentFundingReq, HookPerm::VetoAll],
+            )?;
+        } else {
+            self.assert_hook_perm(caller, &[HookPerm::VetoAll])?;
+        }
+
+        match proposal.status {
+            ProposalStatus::InProgress => Ok(()),
+            ProposalStatus::Approved => {
+                let cooldown = min(
+                    proposal.submission_time + self.vote_duration,
+                    proposal.approved_at.unwrap(),
+                ) + self.cooldown;
+                if cooldown < env::block_timestamp_ms() {
+                    return Err(HookError::CooldownOver);
+                }
+                Ok(())
+            }
+            _ => Err(HookError::ProposalFinalized),
+        }
+    }
+
+    /// Canonical proposal status computation at the given time (in milliseconds), shared by
+    /// the `vote` and `execute` paths so both finalize the status in the same way.
+    fn compute_status(&self, prop: &Proposal, now: u64) -> ProposalStatus {
+        let (members, _) = self.members.get().unwrap();
+        prop.status_at(
+            members.len(),
+            self.threshold,
+            self.min_participation,
+            self.min_vote_duration,
+            self.vote_duration,
+            now,
+        )
+    }
+
     fn assert_proposal(&self, id: u32) -> Proposal {
         self.proposals.get(&id).expect("proposal does not exist")
     }
@@ -487,7 +683,7 @@ impl Contract {
                 }
             }
             PropKind::FunctionCall {
-                receiver_id: _,
+                receiver_id,
                 actions,
             } => {
                 for action in actions {
@@ -498,6 +694,20 @@ impl Contract {
                             return Err(VoteError::NoSelfVote);
                         }
                     }
+                    // we can only check proposals of this house: proposals of other houses
+                    // are not accessible synchronously.
+                    if &action.method_name == "veto_hook"
+                        && *receiver_id == env::current_account_id()
+                    {
+                        let vetoed = serde_json::from_slice::<serde_json::Value>(&action.args.0)
+                            .ok()
+                            .and_then(|args| args["id"].as_u64())
+                            .and_then(|id| u32::try_from(id).ok())
+                            .and_then(|id| self.proposals.get(&id));
+                        if vetoed.map_or(false, |p| &p.proposer == user) {
+                            return Err(VoteError::NoSelfVote);
+                        }
+                    }
                 }
             }
             _ => (),
@@ -513,15 +723,32 @@ impl Contract {
         );
     }
 
-    fn dissolve_and_cleanup(&mut self) {
+    /// Sends the excess account funds to the `recipient`.
+    /// No-op if the DAO is already dissolved.
+    fn dissolve_and_cleanup(&mut self, recipient: AccountId) {
+        if self.dissolved {
+            return;
+        }
         self.dissolved = true;
         emit_dissolve();
         // we leave 10B extra storage
         let required_deposit = (env::storage_usage() + 10) as u128 * env::storage_byte_cost();
         let diff = env::account_balance() - required_deposit;
         if diff > 0 {
-            Promise::new(self.community_fund.clone()).transfer(diff);
+            Promise::new(recipient).transfer(diff);
+        }
+    }
+
+    /// Appends the current `members_len` to the `members_history` (dropping the oldest
+    /// entry when the buffer is full) and emits the `members-update` event.
+    fn record_members_len(&mut self) {
+        let mut history = self.members_history.get().unwrap_or_default();
+        if history.len() >= MEMBERS_HISTORY_LEN {
+            history.remove(0);
         }
+        history.push((env::block_timestamp_ms(), self.members_len));
+        self.members_history.set(&history);
+        emit_members_update(self.members_len);
     }
 
     fn remaining_months(&self, now: u64) -> u64 {
@@ -582,19 +809,27 @@ impl Contract {
     pub fn update_threshold(&mut self) {
         require!(env::predecessor_account_id() == env::current_account_id());
         let (members, _) = self.members.get().unwrap();
-        self.members_len = members.len() as u8;
+        let members_len = members.len() as u8;
+        if members_len != self.members_len {
+            self.members_len = members_len;
+            self.record_members_len();
+        }
         self.threshold = (self.members_len / 2) + 1;
+        self.min_participation = min(self.min_participation, self.members_len);
     }
 }
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod unit_tests {
     use near_sdk::{
-        test_utils::{get_logs, VMContextBuilder},
+        test_utils::{get_created_receipts, get_logs, VMContextBuilder},
         testing_env, VMContext,
     };
 
-    use crate::{view::MembersOutput, *};
+    use crate::{
+        view::{ConfigOutput, MembersOutput},
+        *,
+    };
     use near_sdk::json_types::{U128, U64};
 
     /// 1ms in nano seconds
@@ -631,7 +866,7 @@ mod unit_tests {
         let mut context = VMContextBuilder::new().build();
         let end_time = START + TERM;
         let mut hook_perms = HashMap::new();
-        hook_perms.insert(coa(), vec![HookPerm::VetoAll]);
+        hook_perms.insert(coa(), vec![HookPerm::VetoAll, HookPerm::Freeze]);
         hook_perms.insert(
             voting_body(),
             vec![
@@ -655,11 +890,15 @@ mod unit_tests {
                 PropPerm::FundingRequest,
                 PropPerm::FunctionCall,
                 PropPerm::DismissAndBan,
+                PropPerm::UpdateBigFundingThreshold,
+                PropPerm::UpdateRegistry,
+                PropPerm::UpdateMemberPerms,
             ],
             hook_perms,
             U128(10000),
             U128(1000),
             registry(),
+            0,
         );
         context.block_timestamp = START * MSECOND;
         context.predecessor_account_id = acc(1);
@@ -704,6 +943,71 @@ mod unit_tests {
         };
     }
 
+    #[test]
+    fn get_proposals() {
+        let (_, mut ctr, id1) = setup_ctr(100);
+        let id2 = ctr
+            .create_proposal(PropKind::Text, "Proposal unit test 2".to_string())
+            .unwrap();
+        let id3 = ctr
+            .create_proposal(PropKind::Text, "Proposal unit test 3".to_string())
+            .unwrap();
+        // ProposalOutput is not Clone
+        let prop = |id| ctr.get_proposal(id).unwrap();
+        assert_eq!(ctr.number_of_proposals(), 3);
+        // non reversed
+        assert_eq!(ctr.get_proposals(1, 1, None), vec![prop(id1)]);
+        assert_eq!(
+            ctr.get_proposals(0, 10, None),
+            vec![prop(id1), prop(id2), prop(id3)]
+        );
+        // non reversed with limit
+        assert_eq!(
+            ctr.get_proposals(0, 2, Some(false)),
+            vec![prop(id1), prop(id2)]
+        );
+        assert_eq!(
+            ctr.get_proposals(1, 2, Some(false)),
+            vec![prop(id1), prop(id2)]
+        );
+        // reversed, limit bigger than amount of proposals -> return all
+        assert_eq!(
+            ctr.get_proposals(3, 10, Some(true)),
+            vec![prop(id3), prop(id2), prop(id1)]
+        );
+        // reversed with limit and over the "last proposal"
+        assert_eq!(
+            ctr.get_proposals(5, 2, Some(true)),
+            vec![prop(id3), prop(id2)]
+        );
+
+        // few more edge cases
+        assert_eq!(ctr.get_proposals(1, 0, None), vec![], "limit=0");
+        assert_eq!(
+            ctr.get_proposals(0, 0, Some(true)),
+            vec![],
+            "limit=0 (rev=true)"
+        );
+        assert_eq!(
+            ctr.get_proposals(3, 0, Some(true)),
+            vec![],
+            "limit=0 (rev=true)"
+        );
+        assert_eq!(
+            ctr.get_proposals(0, 1, None),
+            vec![prop(id1)],
+            "0 = start from the first proposal (rev=false)"
+        );
+        assert_eq!(
+            ctr.get_proposals(0, 1, Some(true)),
+            vec![prop(id3)],
+            "0 = start from the last proposal (rev=true)"
+        );
+        assert_eq!(ctr.get_proposals(4, 1, None), vec![]);
+        assert_eq!(ctr.get_proposals(2, 1, None), vec![prop(id2)]);
+        assert_eq!(ctr.get_proposals(2, 1, Some(true)), vec![prop(id2)]);
+    }
+
     #[test]
     fn basic_flow() {
         let (mut ctx, mut ctr, id) = setup_ctr(100);
@@ -739,6 +1043,14 @@ mod unit_tests {
         let res = ctr.get_proposals(3, 1, Some(true));
         assert_eq!(res, vec![ctr.get_proposal(id3).unwrap(),]);
 
+        // batch query
+        let res = ctr.get_proposals_by_ids(vec![id3, 10, id, 0]);
+        assert_eq!(
+            res,
+            vec![ctr.get_proposal(id3), None, ctr.get_proposal(id), None]
+        );
+        assert_eq!(ctr.get_proposals_by_ids(vec![]), vec![]);
+
         ctx.block_timestamp = (START + MIN_VOTE_DURATION + 10) * MSECOND;
         testing_env!(ctx.clone());
         ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
@@ -795,10 +1107,42 @@ mod unit_tests {
     }
 
     #[test]
-    #[should_panic(expected = "proposal does not exist")]
+    fn auto_executed() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        ctr.cooldown = 0;
+        ctr.min_vote_duration = 0;
+        let id2 = ctr
+            .create_proposal(PropKind::Text, "Proposal unit test 2".to_string())
+            .unwrap();
+
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2)].to_vec(), id);
+        assert!(!ctr.auto_executed(id));
+        // deciding vote
+        ctr = vote(ctx.clone(), ctr, [acc(3)].to_vec(), id);
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::Executed
+        );
+        assert!(ctr.auto_executed(id));
+
+        // manual execution
+        ctr.cooldown = COOLDOWN;
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id2);
+        ctx.block_timestamp = (START + COOLDOWN + 1) * MSECOND;
+        testing_env!(ctx);
+        assert_exec_ok(ctr.execute(id2));
+        assert_eq!(
+            ctr.get_proposal(id2).unwrap().proposal.status,
+            ProposalStatus::Executed
+        );
+        assert!(!ctr.auto_executed(id2));
+        assert!(!ctr.auto_executed(id2 + 1));
+    }
+
+    #[test]
     fn proposal_does_not_exist() {
         let (_, mut ctr, _) = setup_ctr(100);
-        ctr.vote(10, Vote::Approve).unwrap();
+        assert_eq!(ctr.vote(10, Vote::Approve), Err(VoteError::PropNotFound));
     }
 
     #[test]
@@ -925,54 +1269,358 @@ mod unit_tests {
     }
 
     #[test]
-    fn proposal_execution_rec_funding_req() {
-        let (mut ctx, mut ctr, _) = setup_ctr(100);
+    fn approved_at() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        assert_eq!(ctr.min_vote_duration(), MIN_VOTE_DURATION);
 
-        let id = ctr
-            .create_proposal(
-                PropKind::RecurrentFundingRequest(U128(10u128)),
-                "Rec Funding req".to_owned(),
-            )
-            .unwrap();
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2)].to_vec(), id);
+        let p = ctr.get_proposal(id).unwrap();
+        assert_eq!(p.proposal.status, ProposalStatus::InProgress);
+        assert_eq!(p.proposal.approved_at, None);
 
-        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 10) * MSECOND;
-        testing_env!(ctx.clone());
-        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        ctx.block_timestamp += 10 * MSECOND;
+        ctr = vote(ctx.clone(), ctr, [acc(3)].to_vec(), id);
+        let p = ctr.get_proposal(id).unwrap();
+        let approved_at = ctx.block_timestamp / MSECOND;
+        assert_eq!(p.proposal.approved_at, Some(approved_at));
+        // approved_at is exposed in the JSON output
+        let out = serde_json::to_value(&p).unwrap();
+        assert_eq!(out["approved_at"], json!(approved_at));
 
-        // update to more than two months
-        ctr.end_time = ctr.start_time + START * 12 * 24 * 61;
-        ctx.block_timestamp = (ctr.start_time + ctr.cooldown + ctr.vote_duration + 1) * MSECOND;
+        // min vote duration is not over yet
+        assert_eq!(p.proposal.status, ProposalStatus::InProgress);
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 1) * MSECOND;
         testing_env!(ctx);
-
-        // proposal isn't executed so budget spent is 0
-        assert_eq!(ctr.budget_spent, 0);
-        assert_exec_ok(ctr.execute(id));
-
-        // budget spent * remaining months
-        assert_eq!(ctr.budget_spent, 20);
+        let p = ctr.get_proposal(id).unwrap();
+        assert_eq!(p.proposal.status, ProposalStatus::Approved);
+        assert_eq!(p.proposal.approved_at, Some(approved_at));
     }
 
     #[test]
-    fn proposal_execution_budget_overflow() {
-        let (mut ctx, mut ctr, _) = setup_ctr(100);
-        ctr.min_vote_duration = 0;
+    fn cancel_proposal() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        ctx.predecessor_account_id = acc(2);
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.cancel_proposal(id), Err(CancelPropError::NotAuthorized));
 
-        // create and approve a funding requst that will fill up the budget.
-        let id1 = ctr
-            .create_proposal(
-                PropKind::FundingRequest((ctr.budget_cap).into()),
-                "Funding req".to_owned(),
-            )
-            .unwrap();
-        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id1);
+        ctx.predecessor_account_id = acc(1);
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.cancel_proposal(id), Ok(()));
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::Cancelled
+        );
+        assert_eq!(
+            get_logs(),
+            vec![
+                r#"EVENT_JSON:{"standard":"ndc-congress","version":"1.0.0","event":"cancel","data":{"prop_id":1}}"#
+            ]
+        );
+        assert_eq!(ctr.cancel_proposal(id), Err(CancelPropError::NotInProgress));
 
-        // create a second proposal, that will go over the budget if proposal id1 is executed
-        let time_diff = 10;
-        ctx.block_timestamp += time_diff * MSECOND;
+        // cancelled proposal can't be voted on nor executed
+        ctx.predecessor_account_id = acc(2);
         testing_env!(ctx.clone());
-        let id2 = ctr
-            .create_proposal(
-                PropKind::FundingRequest(10.into()),
+        assert_eq!(ctr.vote(id, Vote::Approve), Err(VoteError::NotInProgress));
+        assert_eq!(ctr.execute(id).err(), Some(ExecError::NotApproved));
+    }
+
+    #[test]
+    fn cancel_proposal_with_votes() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        ctr = vote(ctx.clone(), ctr, [acc(2)].to_vec(), id);
+        ctx.predecessor_account_id = acc(1);
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.cancel_proposal(id), Err(CancelPropError::HasVotes));
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::InProgress
+        );
+
+        // voting is over
+        let id2 = ctr
+            .create_proposal(PropKind::Text, "Proposal unit test 2".to_string())
+            .unwrap();
+        ctx.block_timestamp = (START + VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(
+            ctr.cancel_proposal(id2),
+            Err(CancelPropError::NotInProgress)
+        );
+    }
+
+    #[test]
+    fn executable_at() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        assert_eq!(ctr.executable_at(id), None);
+        assert_eq!(ctr.executable_at(100), None);
+
+        // approved during the min vote duration: cooldown dominates
+        ctx.block_timestamp += 10 * MSECOND;
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        let at = START + 10 + COOLDOWN + 1;
+        assert_eq!(ctr.executable_at(id), Some(at));
+
+        ctx.block_timestamp = (at - 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.execute(id).err(), Some(ExecError::ExecTime));
+        ctx.block_timestamp = at * MSECOND;
+        testing_env!(ctx.clone());
+        assert!(ctr.execute(id).is_ok());
+        assert_eq!(ctr.executable_at(id), None);
+
+        // approved after the min vote duration
+        ctx.predecessor_account_id = acc(1);
+        ctx.block_timestamp = START * MSECOND;
+        testing_env!(ctx.clone());
+        let id = ctr
+            .create_proposal(PropKind::Text, "Proposal unit test 2".to_string())
+            .unwrap();
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 100) * MSECOND;
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        assert_eq!(
+            ctr.executable_at(id),
+            Some(START + MIN_VOTE_DURATION + 100 + COOLDOWN + 1)
+        );
+
+        // short cooldown: min vote duration dominates
+        ctr.cooldown = 10;
+        ctx.predecessor_account_id = acc(1);
+        ctx.block_timestamp = START * MSECOND;
+        testing_env!(ctx.clone());
+        let id = ctr
+            .create_proposal(PropKind::Text, "Proposal unit test 3".to_string())
+            .unwrap();
+        ctx.block_timestamp = (START + 10) * MSECOND;
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        let at = START + MIN_VOTE_DURATION + 1;
+        assert_eq!(ctr.executable_at(id), Some(at));
+        ctx.block_timestamp = (at - 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.execute(id).err(), Some(ExecError::MinVoteDuration));
+        ctx.block_timestamp = at * MSECOND;
+        testing_env!(ctx);
+        assert!(ctr.execute(id).is_ok());
+    }
+
+    #[test]
+    fn min_participation() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        // 4 members, threshold = 3
+        ctr.min_participation = 4;
+        assert_eq!(ctr.config().min_participation, 4);
+        let id2 = ctr
+            .create_proposal(PropKind::Text, "Proposal unit test 2".to_string())
+            .unwrap();
+
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 1) * MSECOND;
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id2);
+        // enough approvals, but participation is too low
+        let prop = |ctr: &Contract, id| ctr.get_proposal(id).unwrap().proposal;
+        assert_eq!(prop(&ctr, id).status, ProposalStatus::InProgress);
+        assert_eq!(prop(&ctr, id).approved_at, None);
+
+        // the last member abstains: participation is reached
+        ctx.block_timestamp += MSECOND;
+        ctx.predecessor_account_id = acc(4);
+        testing_env!(ctx.clone());
+        ctr.vote(id2, Vote::Abstain).unwrap();
+        assert_eq!(prop(&ctr, id2).status, ProposalStatus::Approved);
+        assert_eq!(
+            prop(&ctr, id2).approved_at,
+            Some(ctx.block_timestamp / MSECOND)
+        );
+
+        // voting is over: rejected regardless of the approvals
+        ctx.block_timestamp = (START + VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(prop(&ctr, id).status, ProposalStatus::Rejected);
+        assert_eq!(ctr.execute(id).err(), Some(ExecError::NotApproved));
+    }
+
+    #[test]
+    fn can_skip_finalize() {
+        let (mut ctx, ctr, id) = setup_ctr(100);
+        // 4 members, threshold = 3
+        let members_num = 4;
+        for ts in [START + 1, START + MIN_VOTE_DURATION + 1] {
+            ctx.block_timestamp = ts * MSECOND;
+            testing_env!(ctx.clone());
+            for approve in 0..=4 {
+                for reject in 0..=(4 - approve) {
+                    for abstain in 0..=(4 - approve - reject) {
+                        let mut p = ctr.get_proposal(id).unwrap().proposal;
+                        p.approve = approve;
+                        p.reject = reject;
+                        p.abstain = abstain;
+                        for i in 0..(approve + reject + abstain) {
+                            p.votes.insert(
+                                acc(i + 1),
+                                VoteRecord {
+                                    timestamp: ts,
+                                    vote: Vote::Abstain,
+                                },
+                            );
+                        }
+                        let skip =
+                            p.can_skip_finalize(members_num, ctr.threshold, MIN_VOTE_DURATION);
+                        p.finalize_status(
+                            members_num,
+                            ctr.threshold,
+                            ctr.min_participation,
+                            MIN_VOTE_DURATION,
+                            VOTE_DURATION,
+                        );
+                        if skip {
+                            // skipping finalize must not change the outcome
+                            assert_eq!(p.status, ProposalStatus::InProgress);
+                        }
+                        // skip is only possible before the min vote duration
+                        if ts > START + MIN_VOTE_DURATION {
+                            assert!(!skip);
+                        }
+                    }
+                }
+            }
+        }
+
+        // vote outcome with the skip check is the same
+        ctx.block_timestamp = (START + 1) * MSECOND;
+        let ctr = vote(ctx.clone(), ctr, [acc(1), acc(2)].to_vec(), id);
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::InProgress
+        );
+        let ctr = vote(ctx, ctr, [acc(3)].to_vec(), id);
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::InProgress
+        );
+    }
+
+    #[test]
+    fn min_participation_dismiss() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+        ctr.min_participation = 4;
+        ctx.predecessor_account_id = voting_body();
+        testing_env!(ctx);
+        ctr.dismiss_hook(acc(4)).unwrap();
+        assert_eq!(ctr.min_participation, 3);
+    }
+
+    #[test]
+    fn members_history() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+        assert_eq!(ctr.members_history(), vec![]);
+
+        ctx.predecessor_account_id = voting_body();
+        ctx.block_timestamp = (START + 10) * MSECOND;
+        testing_env!(ctx.clone());
+        ctr.dismiss_hook(acc(4)).unwrap();
+        assert_eq!(ctr.members_history(), vec![(START + 10, 3)]);
+
+        // dismissing a non member doesn't change the history
+        ctr.dismiss_hook(acc(10)).unwrap();
+        assert_eq!(ctr.members_history().len(), 1);
+
+        ctx.block_timestamp = (START + 20) * MSECOND;
+        testing_env!(ctx);
+        ctr.dismiss_hook(acc(3)).unwrap();
+        assert_eq!(
+            ctr.members_history(),
+            vec![(START + 10, 3), (START + 20, 2)]
+        );
+
+        // the buffer keeps only the latest entries
+        for _ in 0..MEMBERS_HISTORY_LEN {
+            ctr.record_members_len();
+        }
+        let history = ctr.members_history();
+        assert_eq!(history.len(), MEMBERS_HISTORY_LEN);
+        assert!(history.iter().all(|(t, _)| *t == START + 20));
+    }
+
+    #[test]
+    fn committed_budget() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+        let id = ctr
+            .create_proposal(
+                PropKind::FundingRequest(U128(1000u128)),
+                "Funding req".to_owned(),
+            )
+            .unwrap();
+        ctr.create_proposal(
+            PropKind::FundingRequest(U128(500u128)),
+            "Funding req, not approved".to_owned(),
+        )
+        .unwrap();
+        assert_eq!(ctr.committed_budget(), U128(0));
+
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 10) * MSECOND;
+        testing_env!(ctx.clone());
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+
+        // approved but not executed
+        assert_eq!(ctr.budget_spent, 0);
+        assert_eq!(ctr.committed_budget(), U128(1000));
+
+        ctx.block_timestamp = (ctr.start_time + ctr.cooldown + ctr.vote_duration + 1) * MSECOND;
+        testing_env!(ctx);
+        assert_exec_ok(ctr.execute(id));
+        assert_eq!(ctr.budget_spent, 1000);
+        assert_eq!(ctr.committed_budget(), U128(1000));
+    }
+
+    #[test]
+    fn proposal_execution_rec_funding_req() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+
+        let id = ctr
+            .create_proposal(
+                PropKind::RecurrentFundingRequest(U128(10u128)),
+                "Rec Funding req".to_owned(),
+            )
+            .unwrap();
+
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 10) * MSECOND;
+        testing_env!(ctx.clone());
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+
+        // update to more than two months
+        ctr.end_time = ctr.start_time + START * 12 * 24 * 61;
+        ctx.block_timestamp = (ctr.start_time + ctr.cooldown + ctr.vote_duration + 1) * MSECOND;
+        testing_env!(ctx);
+
+        // proposal isn't executed so budget spent is 0
+        assert_eq!(ctr.budget_spent, 0);
+        assert_exec_ok(ctr.execute(id));
+
+        // budget spent * remaining months
+        assert_eq!(ctr.budget_spent, 20);
+    }
+
+    #[test]
+    fn proposal_execution_budget_overflow() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+        ctr.min_vote_duration = 0;
+
+        // create and approve a funding requst that will fill up the budget.
+        let id1 = ctr
+            .create_proposal(
+                PropKind::FundingRequest((ctr.budget_cap).into()),
+                "Funding req".to_owned(),
+            )
+            .unwrap();
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id1);
+
+        // create a second proposal, that will go over the budget if proposal id1 is executed
+        let time_diff = 10;
+        ctx.block_timestamp += time_diff * MSECOND;
+        testing_env!(ctx.clone());
+        let id2 = ctr
+            .create_proposal(
+                PropKind::FundingRequest(10.into()),
                 "Funding req".to_owned(),
             )
             .unwrap();
@@ -998,16 +1646,115 @@ mod unit_tests {
                 err
             ),
         }
+        let logs = get_logs();
+        assert_eq!(logs.len(), 1);
+        let event: serde_json::Value =
+            serde_json::from_str(logs[0].strip_prefix("EVENT_JSON:").unwrap()).unwrap();
+        assert_eq!(event["standard"], "ndc-congress");
+        assert_eq!(event["event"], "budget-exceeded");
+        assert_eq!(event["data"], json!({ "prop_id": id2, "overflow": "10" }));
     }
 
     #[test]
-    #[should_panic(expected = "dao term is over, call dissolve_hook!")]
     fn dao_dissolve_time() {
         let (mut ctx, mut ctr, id) = setup_ctr(100);
         ctx.block_timestamp = (ctr.end_time + 1) * MSECOND;
         testing_env!(ctx);
 
-        ctr.vote(id, Vote::Approve).unwrap();
+        assert_eq!(ctr.vote(id, Vote::Approve), Err(VoteError::TermOver));
+        assert_eq!(
+            ctr.vote_many(vec![(id, Vote::Approve)]),
+            vec![Err(VoteError::TermOver)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "dao term is over, call dissolve_hook!")]
+    fn dao_dissolve_time_create_proposal() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+        ctx.block_timestamp = (ctr.end_time + 1) * MSECOND;
+        testing_env!(ctx);
+
+        ctr.create_proposal(PropKind::Text, "after term".to_string())
+            .unwrap();
+    }
+
+    #[test]
+    fn execute_after_term() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        // cooldown ends after the DAO term
+        ctr.cooldown = TERM;
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 10) * MSECOND;
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        let approved_at = ctr.get_proposal(id).unwrap().proposal.approved_at.unwrap();
+
+        // the cooldown is still required
+        ctx.block_timestamp = (ctr.end_time + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.execute_after_term(id).err(), Some(ExecError::ExecTime));
+
+        ctx.block_timestamp = (approved_at + TERM + 1) * MSECOND;
+        testing_env!(ctx);
+        assert_exec_ok(ctr.execute_after_term(id));
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::Executed
+        );
+        assert_eq!(
+            ctr.execute_after_term(id).err(),
+            Some(ExecError::AlreadyExecuted)
+        );
+    }
+
+    #[test]
+    fn execute_after_term_not_approved() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        // approved only by the execution (min vote duration is not over when voting)
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::InProgress
+        );
+        let id_failed = ctr
+            .create_proposal(PropKind::Text, "failed".to_string())
+            .unwrap();
+        let mut prop = ctr.proposals.get(&id_failed).unwrap();
+        prop.status = ProposalStatus::Failed;
+        ctr.proposals.insert(&id_failed, &prop);
+
+        ctx.block_timestamp = (ctr.end_time + 1) * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(
+            ctr.execute_after_term(id).err(),
+            Some(ExecError::NotApproved)
+        );
+        assert_eq!(
+            ctr.execute_after_term(id_failed).err(),
+            Some(ExecError::NotApproved)
+        );
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::InProgress
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "dao term is not over, use execute")]
+    fn execute_after_term_during_term() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 10) * MSECOND;
+        ctr = vote(ctx, ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        let _ = ctr.execute_after_term(id);
+    }
+
+    #[test]
+    #[should_panic(expected = "dao is dissolved")]
+    fn execute_after_term_dissolved() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        ctr.dissolved = true;
+        ctx.block_timestamp = (ctr.end_time + 1) * MSECOND;
+        testing_env!(ctx);
+        let _ = ctr.execute_after_term(id);
     }
 
     #[test]
@@ -1095,7 +1842,12 @@ mod unit_tests {
             .create_proposal(
                 PropKind::FunctionCall {
                     receiver_id: acc(10),
-                    actions: vec![],
+                    actions: vec![ActionCall {
+                        method_name: "fun".to_string(),
+                        args: Base64VecU8(vec![]),
+                        deposit: U128(0),
+                        gas: U64(EXEC_CTR_CALL_GAS.0),
+                    }],
                 },
                 "function call proposal".to_string(),
             )
@@ -1120,34 +1872,269 @@ mod unit_tests {
             )
             .unwrap();
 
-        (prop_text, prop_fc, prop_big, prop_small, prop_rec)
+        (prop_text, prop_fc, prop_big, prop_small, prop_rec)
+    }
+
+    #[test]
+    fn veto_hook_big_funding_request() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+
+        // CoA should be able to veto everything
+        let (p_text, p_fc, p_big, p_small, p_rec) = create_all_props(&mut ctr);
+        ctx.predecessor_account_id = coa();
+        testing_env!(ctx.clone());
+        ctr.veto_hook(p_text).unwrap();
+        ctr.veto_hook(p_fc).unwrap();
+        ctr.veto_hook(p_big).unwrap();
+        ctr.veto_hook(p_small).unwrap();
+        ctr.veto_hook(p_rec).unwrap();
+
+        // Voting Body should only be able to veto big funding req. or recurrent one.
+        ctx.predecessor_account_id = acc(1);
+        testing_env!(ctx.clone());
+        let (p_text, p_fc, p_big, p_small, p_rec) = create_all_props(&mut ctr);
+        ctx.predecessor_account_id = voting_body();
+        testing_env!(ctx);
+        ctr.veto_hook(p_big).unwrap();
+        ctr.veto_hook(p_rec).unwrap();
+        assert_hook_not_auth(ctr.veto_hook(p_text));
+        assert_hook_not_auth(ctr.veto_hook(p_fc));
+        assert_hook_not_auth(ctr.veto_hook(p_small));
+    }
+
+    #[test]
+    fn get_proposal_live_status() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        let id2 = ctr
+            .create_proposal(PropKind::Text, "Proposal unit test 2".to_string())
+            .unwrap();
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        ctr = vote(ctx.clone(), ctr, [acc(1)].to_vec(), id2);
+
+        // min vote duration didn't pass, so the status can't be finalized yet
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::InProgress
+        );
+
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(
+            ctr.proposals.get(&id).unwrap().status,
+            ProposalStatus::InProgress
+        );
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::Approved
+        );
+        assert_eq!(
+            ctr.get_proposal(id2).unwrap().proposal.status,
+            ProposalStatus::InProgress
+        );
+
+        ctx.block_timestamp = (START + VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(
+            ctr.proposals.get(&id2).unwrap().status,
+            ProposalStatus::InProgress
+        );
+        assert_eq!(
+            ctr.get_proposal(id2).unwrap().proposal.status,
+            ProposalStatus::Rejected
+        );
+        assert_eq!(
+            ctr.get_proposals_by_ids(vec![id, id2])
+                .into_iter()
+                .map(|p| p.unwrap().proposal.status)
+                .collect::<Vec<_>>(),
+            vec![ProposalStatus::Approved, ProposalStatus::Rejected]
+        );
+    }
+
+    #[test]
+    fn status_at_vote_execute_consistency() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        let mut ids = vec![id];
+        for _ in 0..3 {
+            ids.push(
+                ctr.create_proposal(PropKind::Text, "Proposal unit test".to_string())
+                    .unwrap(),
+            );
+        }
+        // ids[0] and ids[1] have enough approvals, ids[2] and ids[3] have too many rejections,
+        // but the min vote duration didn't pass yet.
+        for &id in &ids[0..2] {
+            ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        }
+        for &id in &ids[2..4] {
+            for a in [acc(1), acc(2)] {
+                ctx.predecessor_account_id = a;
+                testing_env!(ctx.clone());
+                ctr.vote(id, Vote::Reject).unwrap();
+            }
+        }
+        for &id in &ids {
+            assert_eq!(
+                ctr.status_at(id, START + 1),
+                Some(ProposalStatus::InProgress)
+            );
+        }
+        assert_eq!(ctr.status_at(100, START + 1), None);
+
+        let now = START + MIN_VOTE_DURATION + 1;
+        assert_eq!(ctr.status_at(ids[0], now), Some(ProposalStatus::Approved));
+        assert_eq!(ctr.status_at(ids[1], now), Some(ProposalStatus::Approved));
+        assert_eq!(ctr.status_at(ids[2], now), Some(ProposalStatus::Rejected));
+        assert_eq!(ctr.status_at(ids[3], now), Some(ProposalStatus::Rejected));
+
+        ctx.block_timestamp = now * MSECOND;
+        // vote path
+        ctx.predecessor_account_id = acc(4);
+        testing_env!(ctx.clone());
+        ctr.vote(ids[0], Vote::Reject).unwrap();
+        assert_eq!(
+            ctr.proposals.get(&ids[0]).unwrap().status,
+            ProposalStatus::Approved
+        );
+        ctx.predecessor_account_id = acc(3);
+        testing_env!(ctx.clone());
+        ctr.vote(ids[2], Vote::Approve).unwrap();
+        assert_eq!(
+            ctr.proposals.get(&ids[2]).unwrap().status,
+            ProposalStatus::Rejected
+        );
+
+        // execute path: approved proposal only waits for the cooldown, rejected one fails
+        assert_eq!(ctr.execute(ids[1]).err(), Some(ExecError::ExecTime));
+        assert_eq!(ctr.execute(ids[3]).err(), Some(ExecError::NotApproved));
+    }
+
+    #[test]
+    fn gas_bounds() {
+        let (_, ctr, _) = setup_ctr(100);
+        let (min_gas, max_gas) = ctr.gas_bounds();
+        assert_eq!(min_gas, U64(8 * Gas::ONE_TERA.0));
+        assert_eq!(max_gas, U64(276 * Gas::ONE_TERA.0));
+        assert!(min_gas < max_gas);
+    }
+
+    #[test]
+    fn function_call_empty_actions() {
+        let (_, mut ctr, _) = setup_ctr(100);
+        assert_eq!(
+            ctr.create_proposal(
+                PropKind::FunctionCall {
+                    receiver_id: acc(10),
+                    actions: vec![],
+                },
+                "".to_string(),
+            ),
+            Err(CreatePropError::BadRequest(
+                "function call proposal must have at least one action".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn update_big_funding_threshold() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+        let id = ctr
+            .create_proposal(
+                PropKind::UpdateBigFundingThreshold(U128(500)),
+                "lower big funding threshold".to_owned(),
+            )
+            .unwrap();
+        // below the current threshold, but above the new one
+        let p_funding = ctr
+            .create_proposal(PropKind::FundingRequest(U128(600)), "funding".to_owned())
+            .unwrap();
+
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 10) * MSECOND;
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+
+        ctx.predecessor_account_id = voting_body();
+        testing_env!(ctx.clone());
+        assert_hook_not_auth(ctr.veto_hook(p_funding));
+
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + COOLDOWN + 20) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_exec_ok(ctr.execute(id));
+        assert_eq!(ctr.big_funding_threshold, 500);
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::Executed
+        );
+
+        // now the voting body can veto the funding request
+        ctr.veto_hook(p_funding).unwrap();
+        assert_eq!(
+            ctr.get_proposal(p_funding).unwrap().proposal.status,
+            ProposalStatus::Vetoed
+        );
     }
 
     #[test]
-    fn veto_hook_big_funding_request() {
-        let (mut ctx, mut ctr, _) = setup_ctr(100);
+    fn prop_kind_serialization() {
+        assert_eq!(
+            serde_json::to_string(&PropKind::UpdateBigFundingThreshold(U128(1000))).unwrap(),
+            r#"{"UpdateBigFundingThreshold":"1000"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<PropKind>(r#"{"UpdateBigFundingThreshold":"1000"}"#).unwrap(),
+            PropKind::UpdateBigFundingThreshold(U128(1000))
+        );
+        assert_eq!(
+            serde_json::to_string(&PropKind::UpdateRegistry(registry())).unwrap(),
+            r#"{"UpdateRegistry":"registry.near"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<PropKind>(r#"{"UpdateRegistry":"registry.near"}"#).unwrap(),
+            PropKind::UpdateRegistry(registry())
+        );
+        let kind = PropKind::UpdateMemberPerms(vec![PropPerm::Text, PropPerm::FundingRequest]);
+        let kind_json = r#"{"UpdateMemberPerms":["Text","FundingRequest"]}"#;
+        assert_eq!(serde_json::to_string(&kind).unwrap(), kind_json);
+        assert_eq!(serde_json::from_str::<PropKind>(kind_json).unwrap(), kind);
+    }
+
+    #[test]
+    fn freeze_hook() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        assert_hook_not_auth(ctr.freeze_hook());
+        assert_hook_not_auth(ctr.unfreeze_hook());
 
-        // CoA should be able to veto everything
-        let (p_text, p_fc, p_big, p_small, p_rec) = create_all_props(&mut ctr);
         ctx.predecessor_account_id = coa();
         testing_env!(ctx.clone());
-        ctr.veto_hook(p_text).unwrap();
-        ctr.veto_hook(p_fc).unwrap();
-        ctr.veto_hook(p_big).unwrap();
-        ctr.veto_hook(p_small).unwrap();
-        ctr.veto_hook(p_rec).unwrap();
+        ctr.freeze_hook().unwrap();
+        let expected = r#"EVENT_JSON:{"standard":"ndc-congress","version":"1.0.0","event":"freeze","data":""}"#;
+        assert_eq!(vec![expected], get_logs());
+        assert!(ctr.is_frozen());
 
-        // Voting Body should only be able to veto big funding req. or recurrent one.
+        // proposal creation is blocked
         ctx.predecessor_account_id = acc(1);
         testing_env!(ctx.clone());
-        let (p_text, p_fc, p_big, p_small, p_rec) = create_all_props(&mut ctr);
-        ctx.predecessor_account_id = voting_body();
+        assert_eq!(
+            ctr.create_proposal(PropKind::Text, "".to_string()),
+            Err(CreatePropError::Frozen)
+        );
+
+        // voting and execution is still allowed
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 10) * MSECOND;
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + COOLDOWN + 20) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_exec_ok(ctr.execute(id));
+
+        ctx.predecessor_account_id = coa();
+        testing_env!(ctx.clone());
+        ctr.unfreeze_hook().unwrap();
+        let expected = r#"EVENT_JSON:{"standard":"ndc-congress","version":"1.0.0","event":"unfreeze","data":""}"#;
+        assert_eq!(vec![expected], get_logs());
+        assert!(!ctr.is_frozen());
+
+        ctx.predecessor_account_id = acc(1);
         testing_env!(ctx);
-        ctr.veto_hook(p_big).unwrap();
-        ctr.veto_hook(p_rec).unwrap();
-        assert_hook_not_auth(ctr.veto_hook(p_text));
-        assert_hook_not_auth(ctr.veto_hook(p_fc));
-        assert_hook_not_auth(ctr.veto_hook(p_small));
+        ctr.create_proposal(PropKind::Text, "".to_string()).unwrap();
     }
 
     #[test]
@@ -1175,6 +2162,96 @@ mod unit_tests {
         .unwrap();
     }
 
+    #[test]
+    fn dissolve_twice() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+        ctx.predecessor_account_id = voting_body();
+        testing_env!(ctx);
+
+        ctr.dissolve_hook().unwrap();
+        ctr.dissolve_hook().unwrap();
+        ctr.dissolve_hook_to(acc(10)).unwrap();
+        let expected = r#"EVENT_JSON:{"standard":"ndc-congress","version":"1.0.0","event":"dissolve","data":""}"#;
+        assert_eq!(vec![expected], get_logs());
+        let receivers: Vec<AccountId> = get_created_receipts()
+            .into_iter()
+            .map(|r| r.receiver_id)
+            .collect();
+        assert_eq!(receivers, vec![community_fund()]);
+    }
+
+    #[test]
+    fn dissolve_hook_to() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+        let recipient = acc(10);
+
+        assert_hook_not_auth(ctr.dissolve_hook_to(recipient.clone()));
+        // coa doesn't have the Dissolve permission
+        ctx.predecessor_account_id = coa();
+        testing_env!(ctx.clone());
+        assert_hook_not_auth(ctr.dissolve_hook_to(recipient.clone()));
+        // permission is required also when the term is over
+        ctx.predecessor_account_id = acc(1);
+        ctx.block_timestamp = (ctr.end_time + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_hook_not_auth(ctr.dissolve_hook_to(recipient.clone()));
+        assert!(!ctr.dissolved);
+
+        ctx.predecessor_account_id = voting_body();
+        testing_env!(ctx);
+        ctr.dissolve_hook_to(recipient.clone()).unwrap();
+        assert!(ctr.dissolved);
+        let receivers: Vec<AccountId> = get_created_receipts()
+            .into_iter()
+            .map(|r| r.receiver_id)
+            .collect();
+        assert_eq!(receivers, vec![recipient]);
+    }
+
+    #[test]
+    #[should_panic(expected = "recipient can't be the congress contract")]
+    fn dissolve_hook_to_self() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+        ctx.predecessor_account_id = voting_body();
+        testing_env!(ctx.clone());
+        ctr.dissolve_hook_to(ctx.current_account_id).unwrap();
+    }
+
+    #[test]
+    fn can_veto() {
+        let (mut ctx, mut ctr, id_text) = setup_ctr(100);
+        // big_funding_threshold = 1000
+        let id_small = ctr
+            .create_proposal(PropKind::FundingRequest(U128(999)), "small".to_owned())
+            .unwrap();
+        let id_big = ctr
+            .create_proposal(PropKind::FundingRequest(U128(1000)), "big".to_owned())
+            .unwrap();
+
+        // voting_body only has VetoBigOrReccurentFundingReq permission
+        assert!(!ctr.can_veto(voting_body(), id_text));
+        assert!(!ctr.can_veto(voting_body(), id_small));
+        assert!(ctr.can_veto(voting_body(), id_big));
+        // coa has VetoAll permission
+        assert!(ctr.can_veto(coa(), id_text));
+        assert!(ctr.can_veto(coa(), id_big));
+        assert!(!ctr.can_veto(acc(1), id_text));
+        assert!(!ctr.can_veto(coa(), id_big + 1));
+
+        // view matches the hook
+        ctx.predecessor_account_id = voting_body();
+        testing_env!(ctx.clone());
+        assert_hook_not_auth(ctr.veto_hook(id_text));
+        assert_eq!(ctr.veto_hook(id_big), Ok(()));
+        // vetoed proposal can't be vetoed again
+        assert!(!ctr.can_veto(coa(), id_big));
+
+        // dao term is over
+        ctx.block_timestamp = (ctr.end_time + 1) * MSECOND;
+        testing_env!(ctx);
+        assert!(!ctr.can_veto(coa(), id_text));
+    }
+
     #[test]
     fn dismiss_hook() {
         let (mut ctx, mut ctr, id) = setup_ctr(100);
@@ -1186,8 +2263,11 @@ mod unit_tests {
         assert_eq!(ctr.dismiss_hook(acc(10)), Ok(()));
         ctr.dismiss_hook(acc(2)).unwrap();
 
-        let expected = r#"EVENT_JSON:{"standard":"ndc-congress","version":"1.0.0","event":"dismiss","data":{"member":"user-2.near"}}"#;
-        assert_eq!(vec![expected], get_logs());
+        let expected = vec![
+            r#"EVENT_JSON:{"standard":"ndc-congress","version":"1.0.0","event":"dismiss","data":{"member":"user-2.near"}}"#,
+            r#"EVENT_JSON:{"standard":"ndc-congress","version":"1.0.0","event":"members-update","data":{"members_len":3}}"#,
+        ];
+        assert_eq!(expected, get_logs());
         assert_eq!(ctr.member_permissions(acc(2)), vec![]);
 
         // Proposal should not pass with only 2 votes
@@ -1317,6 +2397,106 @@ mod unit_tests {
         assert_eq!(prop.proposal.status, ProposalStatus::Failed);
     }
 
+    #[test]
+    fn update_member_perms() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+        let (members, _) = ctr.members.get().unwrap();
+        ctr.members
+            .set(&(members, vec![PropPerm::Text, PropPerm::UpdateMemberPerms]));
+        assert_create_prop_not_allowed(
+            ctr.create_proposal(PropKind::FundingRequest(U128(10)), "funding".to_owned()),
+        );
+
+        // Text permission must remain
+        match ctr.create_proposal(
+            PropKind::UpdateMemberPerms(vec![PropPerm::FundingRequest]),
+            "remove text".to_owned(),
+        ) {
+            Err(err) => assert_eq!(
+                err,
+                CreatePropError::BadRequest("member permissions must include Text".to_owned())
+            ),
+            Ok(_) => panic!("expected Err(CreatePropError::BadRequest)"),
+        }
+
+        let new_perms = vec![
+            PropPerm::Text,
+            PropPerm::UpdateMemberPerms,
+            PropPerm::FundingRequest,
+        ];
+        let id = ctr
+            .create_proposal(
+                PropKind::UpdateMemberPerms(new_perms.clone()),
+                "grant funding request".to_owned(),
+            )
+            .unwrap();
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 10) * MSECOND;
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        ctx.block_timestamp = (START + ctr.vote_duration + ctr.cooldown + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_exec_ok(ctr.execute(id));
+        assert_eq!(ctr.member_permissions(acc(1)), new_perms);
+
+        // previously disallowed proposal kind can be created now
+        // extend the term, so the proposal can still be executed before it ends
+        ctr.end_time += ctr.vote_duration;
+        ctx.predecessor_account_id = acc(1);
+        ctx.block_timestamp = (START + 2 * ctr.vote_duration) * MSECOND;
+        testing_env!(ctx);
+        assert!(ctr
+            .create_proposal(PropKind::FundingRequest(U128(10)), "funding".to_owned())
+            .is_ok());
+    }
+
+    #[test]
+    fn update_registry() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+        let new_registry = AccountId::new_unchecked("registry-2.near".to_string());
+        let id = ctr
+            .create_proposal(
+                PropKind::UpdateRegistry(new_registry.clone()),
+                "update registry".to_owned(),
+            )
+            .unwrap();
+        let motion_rem_ban = ctr
+            .create_proposal(
+                PropKind::DismissAndBan {
+                    member: acc(1),
+                    house: coa(),
+                },
+                "Motion to remove member and ban".to_string(),
+            )
+            .unwrap();
+
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 10) * MSECOND;
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+        ctr = vote(
+            ctx.clone(),
+            ctr,
+            [acc(4), acc(2), acc(3)].to_vec(),
+            motion_rem_ban,
+        );
+
+        ctx.block_timestamp = (START + ctr.vote_duration + ctr.cooldown + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_exec_ok(ctr.execute(id));
+        assert_eq!(ctr.registry, new_registry);
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::Executed
+        );
+
+        // DismissAndBan calls the new registry
+        testing_env!(ctx);
+        assert_exec_ok(ctr.execute(motion_rem_ban));
+        let receivers: Vec<AccountId> = get_created_receipts()
+            .into_iter()
+            .map(|r| r.receiver_id)
+            .collect();
+        assert!(receivers.contains(&new_registry), "got: {:?}", receivers);
+        assert!(!receivers.contains(&registry()), "got: {:?}", receivers);
+    }
+
     #[test]
     fn dismiss_ban_vote_against() {
         let (mut ctx, mut ctr, _) = setup_ctr(100);
@@ -1364,6 +2544,91 @@ mod unit_tests {
         assert_eq!(ctr.vote(prop, Vote::Approve), Err(VoteError::NoSelfVote));
     }
 
+    #[test]
+    fn veto_own_proposal_vote() {
+        // proposal `id` is created by acc(1)
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        let mk_veto = |ctr: &mut Contract, receiver_id: AccountId, vetoed: u64| {
+            ctr.create_proposal(
+                PropKind::FunctionCall {
+                    receiver_id,
+                    actions: [ActionCall {
+                        method_name: "veto_hook".to_string(),
+                        args: Base64VecU8(json!({ "id": vetoed }).to_string().as_bytes().to_vec()),
+                        deposit: U128(0),
+                        gas: U64(EXEC_CTR_CALL_GAS.0),
+                    }]
+                    .to_vec(),
+                },
+                "Proposal to veto".to_string(),
+            )
+            .unwrap()
+        };
+        let prop = mk_veto(&mut ctr, ctx.current_account_id.clone(), id as u64);
+        let prop_other_house = mk_veto(&mut ctr, coa(), id as u64);
+        // the id is not truncated to u32
+        let prop_big_id = mk_veto(
+            &mut ctr,
+            ctx.current_account_id.clone(),
+            id as u64 + (1 << 32),
+        );
+
+        assert_eq!(ctr.vote(prop, Vote::Approve), Err(VoteError::NoSelfVote));
+        // proposals of other houses can't be checked
+        assert_eq!(ctr.vote(prop_other_house, Vote::Approve), Ok(()));
+        assert_eq!(ctr.vote(prop_big_id, Vote::Approve), Ok(()));
+
+        ctx.predecessor_account_id = acc(2);
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.vote(prop, Vote::Approve), Ok(()));
+    }
+
+    #[test]
+    fn vote_many() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        ctr.cooldown = 0;
+        ctr.min_vote_duration = 0;
+        let id2 = ctr
+            .create_proposal(PropKind::Text, "Proposal unit test 2".to_string())
+            .unwrap();
+        let id3 = ctr
+            .create_proposal(PropKind::Text, "Proposal unit test 3".to_string())
+            .unwrap();
+        ctr = vote(ctx.clone(), ctr, vec![acc(1), acc(2)], id2);
+        ctr = vote(ctx.clone(), ctr, vec![acc(3)], id3);
+
+        ctx.predecessor_account_id = acc(3);
+        testing_env!(ctx);
+        let res = ctr.vote_many(vec![
+            (id, Vote::Reject),
+            (id2, Vote::Approve),
+            (100, Vote::Approve),
+            (id3, Vote::Approve),
+        ]);
+        assert_eq!(
+            res,
+            vec![
+                Ok(()),
+                Ok(()),
+                Err(VoteError::PropNotFound),
+                Err(VoteError::DoubleVote)
+            ]
+        );
+
+        let prop = |id| ctr.get_proposal(id).unwrap();
+        assert_eq!(prop(id).proposal.reject, 1);
+        assert_eq!(prop(id3).proposal.approve, 1);
+        // id2 reached the threshold and, with cooldown = 0, is executed immediately
+        assert_eq!(prop(id2).proposal.approve, 3);
+        assert_eq!(prop(id2).proposal.status, ProposalStatus::Executed);
+
+        ctr.dissolved = true;
+        assert_eq!(
+            ctr.vote_many(vec![(id3, Vote::Reject)]),
+            vec![Err(VoteError::Dissolved)]
+        );
+    }
+
     #[test]
     fn abstain_vote() {
         let (_, mut ctr, id) = setup_ctr(100);
@@ -1401,6 +2666,28 @@ mod unit_tests {
         );
     }
 
+    #[test]
+    fn execute_rejected_event() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        ctx.block_timestamp = (START + MIN_VOTE_DURATION + 10) * MSECOND;
+        ctr = vote(ctx.clone(), ctr, [acc(1), acc(2), acc(3)].to_vec(), id);
+
+        testing_env!(ctx.clone());
+        match ctr.execute(id) {
+            Ok(_) => panic!("expecting Err"),
+            Err(err) => assert_eq!(err, ExecError::ExecTime),
+        };
+        let logs = get_logs();
+        assert_eq!(logs.len(), 1);
+        let event: serde_json::Value =
+            serde_json::from_str(logs[0].strip_prefix("EVENT_JSON:").unwrap()).unwrap();
+        assert_eq!(event["event"], "execute-rejected");
+        assert_eq!(
+            event["data"],
+            json!({ "prop_id": id, "reason": "ExecTime" })
+        );
+    }
+
     #[test]
     fn min_vote_duration_execute() {
         let (mut ctx, mut ctr, id) = setup_ctr(100);
@@ -1460,4 +2747,115 @@ mod unit_tests {
         let (_, ctr, _) = setup_ctr(100);
         assert_eq!(ctr.members_len(), 4);
     }
+
+    #[test]
+    fn config() {
+        let (_, ctr, _) = setup_ctr(100);
+        assert_eq!(
+            ctr.config(),
+            ConfigOutput {
+                community_fund: community_fund(),
+                registry: registry(),
+                dissolved: false,
+                threshold: 3,
+                min_participation: 0,
+                start_time: START,
+                end_time: START + TERM,
+                cooldown: COOLDOWN,
+                vote_duration: VOTE_DURATION,
+                budget_spent: U128(0),
+                budget_cap: U128(10000),
+                big_funding_threshold: U128(1000),
+                min_vote_duration: MIN_VOTE_DURATION,
+            }
+        );
+    }
+
+    #[test]
+    fn non_voters() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        assert_eq!(ctr.non_voters(id), vec![acc(1), acc(2), acc(3), acc(4)]);
+        assert_eq!(ctr.non_voters(id + 1), vec![]);
+
+        ctr = vote(ctx.clone(), ctr, [acc(2), acc(3)].to_vec(), id);
+        assert_eq!(ctr.non_voters(id), vec![acc(1), acc(4)]);
+
+        // proposal is not in progress any more
+        ctx.block_timestamp = (START + VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(ctr.non_voters(id), vec![]);
+    }
+
+    #[test]
+    fn my_pending_proposals() {
+        let (mut ctx, mut ctr, id) = setup_ctr(100);
+        let dismiss_acc2 = ctr
+            .create_proposal(
+                PropKind::DismissAndBan {
+                    member: acc(2),
+                    house: coa(),
+                },
+                "Motion to remove member and ban".to_string(),
+            )
+            .unwrap();
+        assert_eq!(ctr.my_pending_proposals(acc(1)), vec![id, dismiss_acc2]);
+        // the member can't vote on the proposal against them
+        assert_eq!(ctr.my_pending_proposals(acc(2)), vec![id]);
+        // not a member
+        assert_eq!(ctr.my_pending_proposals(acc(10)), vec![]);
+
+        ctr = vote(ctx.clone(), ctr, [acc(1)].to_vec(), id);
+        assert_eq!(ctr.my_pending_proposals(acc(1)), vec![dismiss_acc2]);
+        assert_eq!(ctr.my_pending_proposals(acc(3)), vec![id, dismiss_acc2]);
+
+        // voting window is over
+        ctx.block_timestamp = (START + VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(ctr.my_pending_proposals(acc(3)), vec![]);
+    }
+
+    #[test]
+    fn term_remaining() {
+        let (mut ctx, ctr, _) = setup_ctr(100);
+        assert_eq!(ctr.term_remaining(), TERM);
+
+        ctx.block_timestamp = (ctr.end_time - 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.term_remaining(), 1);
+
+        ctx.block_timestamp = ctr.end_time * MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.term_remaining(), 0);
+
+        ctx.block_timestamp = (ctr.end_time + 10) * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(ctr.term_remaining(), 0);
+    }
+
+    #[test]
+    fn create_proposal_close_to_term_end() {
+        let (mut ctx, mut ctr, _) = setup_ctr(100);
+        let last_ok = ctr.end_time - ctr.vote_duration - ctr.cooldown;
+
+        ctx.block_timestamp = last_ok * MSECOND;
+        testing_env!(ctx.clone());
+        assert!(ctr.create_proposal(PropKind::Text, "".to_string()).is_ok());
+
+        ctx.block_timestamp = (last_ok + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        match ctr.create_proposal(PropKind::Text, "".to_string()) {
+            Err(CreatePropError::BadRequest(_)) => (),
+            x => panic!("expected BadRequest, got: {:?}", x),
+        }
+
+        // without cooldown the proposal only needs to fit the voting window
+        ctr.cooldown = 0;
+        assert!(ctr.create_proposal(PropKind::Text, "".to_string()).is_ok());
+        ctx.block_timestamp = (ctr.end_time - ctr.vote_duration + 1) * MSECOND;
+        testing_env!(ctx);
+        assert!(matches!(
+            ctr.create_proposal(PropKind::Text, "".to_string()),
+            Err(CreatePropError::BadRequest(_))
+        ));
+    }
 }
@@ -43,16 +43,15 @@ impl Proposal {
         user: AccountId,
         vote: Vote,
         threshold: u8,
+        min_participation: u8,
     ) -> Result<(), VoteError> {
         if self.votes.contains_key(&user) {
             return Err(VoteError::DoubleVote);
         }
+        let is_approve = matches!(vote, Vote::Approve);
         match vote {
             Vote::Approve => {
                 self.approve += 1;
-                if self.approve >= threshold {
-                    self.approved_at = Some(env::block_timestamp_ms());
-                }
             }
             Vote::Reject => {
                 self.reject += 1;
@@ -61,6 +60,14 @@ impl Proposal {
                 self.abstain += 1;
             }
         }
+        // approval is reached by an approve vote, or by a vote reaching the min participation
+        let participation = self.participation();
+        if self.approve >= threshold
+            && participation >= min_participation
+            && (is_approve || participation == min_participation)
+        {
+            self.approved_at = Some(env::block_timestamp_ms());
+        }
         self.votes.insert(
             user,
             VoteRecord {
@@ -72,36 +79,88 @@ impl Proposal {
         Ok(())
     }
 
+    /// Number of members who voted (approve, reject or abstain).
+    pub fn participation(&self) -> u8 {
+        self.approve + self.reject + self.abstain
+    }
+
     /// Returns true if it's past min voting duration
+    /// A proposal is approved only when it has at least `threshold` approve votes and
+    /// `min_participation` votes in total. Otherwise it's rejected once the voting is over.
     pub fn finalize_status(
         &mut self,
         members_num: usize,
         threshold: u8,
+        min_participation: u8,
         min_vote_duration: u64,
         vote_duration: u64,
     ) -> bool {
         if !matches!(self.status, ProposalStatus::InProgress) {
             return true;
         }
-        let past_min_vote_duration = self.past_min_vote_duration(min_vote_duration);
+        let now = env::block_timestamp_ms();
+        self.status = self.status_at(
+            members_num,
+            threshold,
+            min_participation,
+            min_vote_duration,
+            vote_duration,
+            now,
+        );
+        self.past_min_vote_duration_at(min_vote_duration, now)
+    }
+
+    /// Returns the proposal status at the given time (in milliseconds), without modifying
+    /// the proposal. Only `InProgress` proposals can change the status.
+    pub fn status_at(
+        &self,
+        members_num: usize,
+        threshold: u8,
+        min_participation: u8,
+        min_vote_duration: u64,
+        vote_duration: u64,
+        now: u64,
+    ) -> ProposalStatus {
+        if !matches!(self.status, ProposalStatus::InProgress) {
+            return self.status.clone();
+        }
         let all_voted = self.votes.len() == members_num;
-        if past_min_vote_duration || all_voted {
-            if self.approve >= threshold {
-                self.status = ProposalStatus::Approved;
+        if self.past_min_vote_duration_at(min_vote_duration, now) || all_voted {
+            if self.approve >= threshold && self.participation() >= min_participation {
+                return ProposalStatus::Approved;
             } else if self.reject + self.abstain > members_num as u8 - threshold
-                || env::block_timestamp_ms() > self.submission_time + vote_duration
+                || now > self.submission_time + vote_duration
             {
-                self.status = ProposalStatus::Rejected;
+                return ProposalStatus::Rejected;
             }
         }
-        past_min_vote_duration
+        ProposalStatus::InProgress
+    }
+
+    /// Returns true if the proposal status can't change yet: neither the approval nor the
+    /// rejection threshold is reached, not all members voted and it's before the min vote
+    /// duration. In such case `finalize_status` is a no-op and can be skipped.
+    pub fn can_skip_finalize(
+        &self,
+        members_num: usize,
+        threshold: u8,
+        min_vote_duration: u64,
+    ) -> bool {
+        self.approve < threshold
+            && self.reject + self.abstain <= members_num as u8 - threshold
+            && self.votes.len() < members_num
+            && !self.past_min_vote_duration(min_vote_duration)
     }
 
     pub fn past_min_vote_duration(&self, min_vote_duration: u64) -> bool {
+        self.past_min_vote_duration_at(min_vote_duration, env::block_timestamp_ms())
+    }
+
+    pub fn past_min_vote_duration_at(&self, min_vote_duration: u64, now: u64) -> bool {
         if min_vote_duration == 0 {
             return true;
         }
-        self.submission_time + min_vote_duration < env::block_timestamp_ms()
+        self.submission_time + min_vote_duration < now
     }
 }
 
@@ -133,6 +192,13 @@ pub enum PropKind {
         member: AccountId,
         house: AccountId,
     },
+    /// Updates the `big_funding_threshold`: funding requests with amount bigger or equal
+    /// to the threshold are eligible for `VetoBigOrReccurentFundingReq`.
+    UpdateBigFundingThreshold(U128),
+    /// Updates the I Am Human `registry` used to ban accounts in `DismissAndBan`.
+    UpdateRegistry(AccountId),
+    /// Replaces the proposal permissions of all members. Must include `Text`.
+    UpdateMemberPerms(Vec<PropPerm>),
 }
 
 impl PropKind {
@@ -143,6 +209,9 @@ impl PropKind {
             PropKind::FundingRequest { .. } => PropPerm::FundingRequest,
             PropKind::RecurrentFundingRequest { .. } => PropPerm::RecurrentFundingRequest,
             PropKind::DismissAndBan { .. } => PropPerm::DismissAndBan,
+            PropKind::UpdateBigFundingThreshold(_) => PropPerm::UpdateBigFundingThreshold,
+            PropKind::UpdateRegistry(_) => PropPerm::UpdateRegistry,
+            PropKind::UpdateMemberPerms(_) => PropPerm::UpdateMemberPerms,
         }
     }
 
@@ -154,11 +223,14 @@ impl PropKind {
             PropKind::FundingRequest { .. } => "funding-request".to_string(),
             PropKind::RecurrentFundingRequest { .. } => "recurrent-funding-request".to_string(),
             PropKind::DismissAndBan { .. } => "remove-and-ban".to_string(),
+            PropKind::UpdateBigFundingThreshold(_) => "update-big-funding-threshold".to_string(),
+            PropKind::UpdateRegistry(_) => "update-registry".to_string(),
+            PropKind::UpdateMemberPerms(_) => "update-member-perms".to_string(),
         }
     }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Serialize)]
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
 #[cfg_attr(any(test, not(target_arch = "wasm32")), derive(Deserialize))]
@@ -171,6 +243,8 @@ pub enum ProposalStatus {
     Failed,
     // note: In Astra++ we have also: Removed nor Moved
     Vetoed,
+    /// Proposal cancelled by the proposer before any vote was cast.
+    Cancelled,
 }
 
 /// Votes recorded in the proposal.
@@ -205,8 +279,8 @@ pub struct ActionCall {
 }
 
 /// Permissions for creating proposals. See PropposalKind for more information.
-#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(test, derive(Debug, Clone))]
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
 #[serde(crate = "near_sdk::serde")]
 pub enum PropPerm {
     FunctionCall,
@@ -214,6 +288,9 @@ pub enum PropPerm {
     FundingRequest,
     RecurrentFundingRequest,
     DismissAndBan,
+    UpdateBigFundingThreshold,
+    UpdateRegistry,
+    UpdateMemberPerms,
 }
 
 /// Permissions for calling hooks
@@ -227,6 +304,8 @@ pub enum HookPerm {
     VetoBigOrReccurentFundingReq,
     Dismiss,
     Dissolve,
+    /// Allows to freeze and unfreeze the proposal creation
+    Freeze,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Serialize)]
@@ -7,4 +7,6 @@ pub enum StorageKey {
     Proposals,
     Members,
     HookAuth,
+    MembersHistory,
+    AutoExecuted,
 }
@@ -1,6 +1,7 @@
 use std::cmp::{max, min};
 
 use itertools::Either;
+use near_sdk::json_types::U64;
 #[allow(unused_imports)]
 use near_sdk::serde::{Deserialize, Serialize};
 
@@ -20,9 +21,14 @@ pub struct ProposalOutput {
 
 /// This is format of output via JSON for the config.
 #[derive(Serialize)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
 #[serde(crate = "near_sdk::serde")]
 pub struct ConfigOutput {
+    pub community_fund: AccountId,
+    pub registry: AccountId,
+    pub dissolved: bool,
     pub threshold: u8,
+    pub min_participation: u8,
     pub start_time: u64,
     pub end_time: u64,
     pub cooldown: u64,
@@ -49,14 +55,18 @@ impl Contract {
      * QUERIES
      **********/
 
-    /// Returns all proposals
-    /// Get proposals in paginated view.
+    /// Returns proposals in paginated view. Proposal ids start from 1.
+    /// If `from_index == 0` then it will start from the first proposal (or the last one if
+    /// reverse is set to true). Returns an empty list if `limit == 0`.
     pub fn get_proposals(
         &self,
         from_index: u32,
         limit: u32,
         reverse: Option<bool>,
     ) -> Vec<ProposalOutput> {
+        if limit == 0 {
+            return Vec::new();
+        }
         let iter = if reverse.unwrap_or(false) {
             let end = if from_index == 0 {
                 self.prop_counter
@@ -78,6 +88,7 @@ impl Contract {
                 proposal.finalize_status(
                     ml,
                     self.threshold,
+                    self.min_participation,
                     self.min_vote_duration,
                     self.vote_duration,
                 );
@@ -88,6 +99,9 @@ impl Contract {
     }
 
     /// Get specific proposal.
+    /// The returned status is computed live: an `InProgress` proposal past its voting window
+    /// is returned as `Approved` or `Rejected`, even if it was not executed yet and the stored
+    /// status is still `InProgress`.
     pub fn get_proposal(&self, id: u32) -> Option<ProposalOutput> {
         let (members, _) = self.members.get().unwrap();
         let ml = members.len();
@@ -95,6 +109,7 @@ impl Contract {
             proposal.finalize_status(
                 ml,
                 self.threshold,
+                self.min_participation,
                 self.min_vote_duration,
                 self.vote_duration,
             );
@@ -102,6 +117,122 @@ impl Contract {
         })
     }
 
+    /// Returns the status the proposal would have at the given time (in milliseconds), using
+    /// the same computation as `vote` and `execute`. Returns `None` if the proposal doesn't
+    /// exist.
+    pub fn status_at(&self, id: u32, now: u64) -> Option<ProposalStatus> {
+        self.proposals
+            .get(&id)
+            .map(|proposal| self.compute_status(&proposal, now))
+    }
+
+    /// Returns proposals with the given ids, in the same order. `None` is returned for
+    /// proposals which don't exist.
+    pub fn get_proposals_by_ids(&self, ids: Vec<u32>) -> Vec<Option<ProposalOutput>> {
+        let (members, _) = self.members.get().unwrap();
+        let ml = members.len();
+        ids.into_iter()
+            .map(|id| {
+                self.proposals.get(&id).map(|mut proposal| {
+                    proposal.finalize_status(
+                        ml,
+                        self.threshold,
+                        self.min_participation,
+                        self.min_vote_duration,
+                        self.vote_duration,
+                    );
+                    ProposalOutput { id, proposal }
+                })
+            })
+            .collect()
+    }
+
+    /// Returns true if the proposal was executed automatically by the deciding vote (when
+    /// `cooldown == 0`), and false if it was executed with `execute` or not executed at all.
+    /// NOTE: proposals auto executed before the contract migration are not marked.
+    pub fn auto_executed(&self, id: u32) -> bool {
+        self.auto_executed.contains(&id)
+    }
+
+    /// Returns members who didn't vote yet on the given proposal.
+    /// Returns an empty list if the proposal doesn't exist or is not in progress.
+    pub fn non_voters(&self, id: u32) -> Vec<AccountId> {
+        let (members, _) = self.members.get().unwrap();
+        let mut proposal = match self.proposals.get(&id) {
+            Some(p) => p,
+            None => return vec![],
+        };
+        proposal.finalize_status(
+            members.len(),
+            self.threshold,
+            self.min_participation,
+            self.min_vote_duration,
+            self.vote_duration,
+        );
+        if !matches!(proposal.status, ProposalStatus::InProgress) {
+            return vec![];
+        }
+        members
+            .into_iter()
+            .filter(|m| !proposal.votes.contains_key(m))
+            .collect()
+    }
+
+    /// Returns ids of the proposals the `member` can still vote on: in progress proposals
+    /// within the voting window, which the member didn't vote on yet and is not involved in
+    /// (eg a `DismissAndBan` proposal against the member).
+    /// Returns an empty list if `member` is not a house member or the DAO is not active.
+    pub fn my_pending_proposals(&self, member: AccountId) -> Vec<u32> {
+        let (members, _) = self.members.get().unwrap();
+        let now = env::block_timestamp_ms();
+        if self.dissolved || now > self.end_time || members.binary_search(&member).is_err() {
+            return vec![];
+        }
+        (1..=self.prop_counter)
+            .filter(|id| {
+                self.proposals.get(id).map_or(false, |p| {
+                    matches!(p.status, ProposalStatus::InProgress)
+                        && now <= p.submission_time + self.vote_duration
+                        && !p.votes.contains_key(&member)
+                        && self.assert_member_not_involved(&p, &member).is_ok()
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the timestamp (in milliseconds) from which `execute` of an approved proposal
+    /// will first succeed: past the `min_vote_duration` (unless the proposal was already
+    /// finalized because all members voted) and past the `cooldown` since the approval.
+    /// Returns `None` if the proposal doesn't exist, is not approved or was already executed.
+    pub fn executable_at(&self, id: u32) -> Option<u64> {
+        let (members, _) = self.members.get().unwrap();
+        let mut proposal = self.proposals.get(&id)?;
+        let finalized = !matches!(proposal.status, ProposalStatus::InProgress);
+        proposal.finalize_status(
+            members.len(),
+            self.threshold,
+            self.min_participation,
+            self.min_vote_duration,
+            self.vote_duration,
+        );
+        if !matches!(
+            proposal.status,
+            ProposalStatus::InProgress | ProposalStatus::Approved | ProposalStatus::Failed
+        ) {
+            return None;
+        }
+        // InProgress proposal with `approved_at` will be approved after the min_vote_duration
+        let approved_at = proposal.approved_at?;
+        let mut at = approved_at;
+        if !finalized && self.min_vote_duration > 0 {
+            at = max(at, proposal.submission_time + self.min_vote_duration + 1);
+        }
+        if self.cooldown > 0 {
+            at = max(at, approved_at + self.cooldown + 1);
+        }
+        Some(at)
+    }
+
     pub fn number_of_proposals(&self) -> u32 {
         self.prop_counter
     }
@@ -110,6 +241,29 @@ impl Contract {
         self.dissolved
     }
 
+    /// Returns true if the proposal creation is frozen.
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Returns the time (in milliseconds) remaining until the end of the DAO term.
+    /// Returns 0 if the term is over.
+    pub fn term_remaining(&self) -> u64 {
+        self.end_time.saturating_sub(env::block_timestamp_ms())
+    }
+
+    /// Returns the latest `(timestamp_ms, members_len)` entries recorded after each members
+    /// change (at most `MEMBERS_HISTORY_LEN`), oldest first.
+    pub fn members_history(&self) -> Vec<(u64, u8)> {
+        self.members_history.get().unwrap_or_default()
+    }
+
+    /// Returns the minimum vote duration (in milliseconds): a proposal which reached the
+    /// approval threshold can't be finalized before this time, unless all members voted.
+    pub fn min_vote_duration(&self) -> u64 {
+        self.min_vote_duration
+    }
+
     pub fn members_len(&self) -> u8 {
         self.members_len
     }
@@ -146,14 +300,32 @@ impl Contract {
         hooks.remove(&user).unwrap_or(vec![])
     }
 
+    /// Returns true if the `dao` is allowed to veto the proposal right now (has the required
+    /// veto permission for the proposal kind, and the proposal is not finalized).
+    /// Returns false if the proposal doesn't exist.
+    pub fn can_veto(&self, dao: AccountId, id: u32) -> bool {
+        if self.dissolved || env::block_timestamp_ms() > self.end_time {
+            return false;
+        }
+        match self.proposals.get(&id) {
+            Some(proposal) => self.check_veto(&dao, &proposal).is_ok(),
+            None => false,
+        }
+    }
+
     /// Returns all hook permissions
     pub fn all_hook_permissions(&self) -> HashMap<AccountId, Vec<HookPerm>> {
         self.hook_auth.get().unwrap()
     }
 
+    /// Returns all contract parameters.
     pub fn config(&self) -> ConfigOutput {
         ConfigOutput {
+            community_fund: self.community_fund.clone(),
+            registry: self.registry.clone(),
+            dissolved: self.dissolved,
             threshold: self.threshold,
+            min_participation: self.min_participation,
             start_time: self.start_time,
             end_time: self.end_time,
             cooldown: self.cooldown,
@@ -164,4 +336,39 @@ impl Contract {
             min_vote_duration: self.min_vote_duration,
         }
     }
+
+    /// Returns `budget_spent` plus the budget of approved, but not yet executed funding
+    /// requests. Recurrent funding requests are counted for the remaining months of the term.
+    pub fn committed_budget(&self) -> U128 {
+        let (members, _) = self.members.get().unwrap();
+        let ml = members.len();
+        let months = self.remaining_months(env::block_timestamp_ms()) as u128;
+        let mut committed = self.budget_spent;
+        for id in 1..=self.prop_counter {
+            if let Some(mut proposal) = self.proposals.get(&id) {
+                proposal.finalize_status(
+                    ml,
+                    self.threshold,
+                    self.min_participation,
+                    self.min_vote_duration,
+                    self.vote_duration,
+                );
+                if !matches!(proposal.status, ProposalStatus::Approved) {
+                    continue;
+                }
+                match proposal.kind {
+                    PropKind::FundingRequest(b) => committed += b.0,
+                    PropKind::RecurrentFundingRequest(b) => committed += b.0 * months,
+                    _ => (),
+                }
+            }
+        }
+        U128(committed)
+    }
+
+    /// Returns the (min, max) gas bounds for a single `FunctionCall` proposal action.
+    /// The sum of gas of all actions can't exceed the max bound either.
+    pub fn gas_bounds(&self) -> (U64, U64) {
+        (U64(EXEC_CTR_CALL_GAS.0), U64(MAX_EXEC_FUN_CALL_GAS.0))
+    }
 }
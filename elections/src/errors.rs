@@ -1,7 +1,7 @@
 use near_sdk::env::panic_str;
 use near_sdk::FunctionError;
 
-use crate::TokenId;
+use crate::{TokenId, MAX_REF_LINK_LEN, MIN_REF_LINK_LEN};
 
 /// Contract errors
 #[cfg_attr(not(target_arch = "wasm32"), derive(PartialEq, Debug))]
@@ -58,3 +58,40 @@ impl FunctionError for RevokeVoteError {
         }
     }
 }
+
+/// Proposal creation errors
+#[cfg_attr(not(target_arch = "wasm32"), derive(PartialEq, Debug))]
+pub enum CreateProposalError {
+    StartInPast,
+    /// proposal start is before `now + min_start_lead_ms`. Holds the `min_start_lead_ms`.
+    StartTooSoon(u64),
+    EndBeforeStart,
+    WrongSeats,
+    RefLinkLength,
+    SetupPackageSeats,
+    SetupPackageCandidates,
+    DuplicatedCandidates,
+}
+
+impl FunctionError for CreateProposalError {
+    fn panic(&self) -> ! {
+        match self {
+            CreateProposalError::StartInPast => panic_str("proposal start must be in the future"),
+            CreateProposalError::StartTooSoon(lead) => panic_str(&format!(
+                "proposal start must be at least {}ms after now",
+                lead
+            )),
+            CreateProposalError::EndBeforeStart => panic_str("proposal start must be before end"),
+            CreateProposalError::WrongSeats => panic_str("require 0 < seats <= candidates.length"),
+            CreateProposalError::RefLinkLength => panic_str(&format!(
+                "ref_link length must be between {} and {} bytes",
+                MIN_REF_LINK_LEN, MAX_REF_LINK_LEN
+            )),
+            CreateProposalError::SetupPackageSeats => panic_str("SetupPackage seats must equal 1"),
+            CreateProposalError::SetupPackageCandidates => {
+                panic_str("SetupPackage candidates must be ['yes', 'no', 'abstain']")
+            }
+            CreateProposalError::DuplicatedCandidates => panic_str("duplicated candidates"),
+        }
+    }
+}
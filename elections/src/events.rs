@@ -1,4 +1,4 @@
-use near_sdk::{serde::Serialize, Balance};
+use near_sdk::{serde::Serialize, AccountId, Balance};
 use serde_json::json;
 
 use common::{EventPayload, NearEvent};
@@ -33,9 +33,23 @@ pub(crate) fn emit_revoke_vote(prop_id: u32) {
     })
 }
 
+pub(crate) fn emit_candidates_disqualified(accounts: &[AccountId]) {
+    emit_event(EventPayload {
+        event: "candidates_disqualified",
+        data: json!({ "accounts": accounts }),
+    });
+}
+
+pub(crate) fn emit_candidate_requalified(account: &AccountId) {
+    emit_event(EventPayload {
+        event: "candidate_requalified",
+        data: json!({ "account": account }),
+    });
+}
+
 #[cfg(test)]
 mod unit_tests {
-    use near_sdk::{test_utils, AccountId};
+    use near_sdk::test_utils;
 
     use super::*;
 
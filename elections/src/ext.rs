@@ -16,6 +16,11 @@ pub trait ExtSelf {
         voter: AccountId,
         vote: Vote,
     ) -> Result<(), VoteError>;
+    fn on_vote_many_verified(
+        &mut self,
+        voter: AccountId,
+        votes: Vec<(u32, Vote)>,
+    ) -> Result<(), VoteError>;
     fn on_revoke_verified(&mut self, prop_id: u32, user: AccountId) -> Result<(), RevokeVoteError>;
 }
 
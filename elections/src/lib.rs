@@ -1,12 +1,15 @@
 use std::cmp::max;
 use std::collections::HashSet;
 
-use events::{emit_bond, emit_revoke_vote, emit_vote};
+use events::{
+    emit_bond, emit_candidate_requalified, emit_candidates_disqualified, emit_revoke_vote,
+    emit_vote,
+};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap};
+use near_sdk::collections::{LazyOption, LookupMap, LookupSet};
 use near_sdk::json_types::U128;
 use near_sdk::{
-    env, near_bindgen, require, AccountId, Gas, PanicOnDefault, Promise, PromiseOrValue,
+    env, near_bindgen, require, AccountId, Balance, Gas, PanicOnDefault, Promise, PromiseOrValue,
 };
 use sbt::{ClassId, ClassMetadata};
 
@@ -52,17 +55,50 @@ pub struct Contract {
 
     /// class metadata for I-Voted SBT
     pub class_metadata: LookupMap<ClassId, ClassMetadata>,
+
+    /// set of proposals cancelled by the admin
+    pub cancelled_proposals: LookupSet<u32>,
+
+    /// minimum time (in milliseconds) between the proposal creation and the proposal start.
+    pub min_start_lead_ms: u64,
+
+    /// number of IAH tokens with a bond.
+    pub bonded_count: u32,
+    /// number of accounts which accepted the Fair Voting Policy in the current policy epoch.
+    pub policy_count: u32,
+
+    /// number of blank ballots (empty votes) per proposal.
+    pub blank_ballots: LookupMap<u32, u64>,
+
+    /// set of proposals which allow voters to change their vote.
+    pub vote_change_allowed: LookupSet<u32>,
+
+    /// account which can call the cleanup methods (in addition to the `authority`) after the
+    /// `finish_time`. See `assert_post_finish_authority`.
+    pub post_finish_authority: Option<AccountId>,
+
+    /// number of accounts which accepted a given policy (by the policy epoch and hash).
+    pub policy_accept_count: LookupMap<(u64, [u8; 32]), u64>,
+
+    /// policy version, increased on every `admin_set_policy`.
+    pub policy_epoch: u64,
+    /// policy epoch in which the account accepted the policy. Acceptances from previous epochs
+    /// are stale and must be renewed.
+    pub accepted_policy_epoch: LookupMap<AccountId, u64>,
 }
 
 #[near_bindgen]
 impl Contract {
     #[init]
     /// * `policy` is a blake2s-256 hex-encoded hash of the Fair Voting Policy text.
+    /// * `min_start_lead_ms`: minimum time (in milliseconds) between the proposal creation
+    ///   and the proposal start.
     pub fn new(
         authority: AccountId,
         sbt_registry: AccountId,
         policy: String,
         finish_time: u64,
+        min_start_lead_ms: u64,
     ) -> Self {
         let policy = assert_hash_hex_string(&policy);
 
@@ -79,6 +115,16 @@ impl Contract {
             finish_time,
             disqualified_candidates: LazyOption::new(StorageKey::DisqualifiedCandidates, None),
             class_metadata: LookupMap::new(StorageKey::ClassMetadata),
+            cancelled_proposals: LookupSet::new(StorageKey::CancelledProposals),
+            min_start_lead_ms,
+            bonded_count: 0,
+            policy_count: 0,
+            blank_ballots: LookupMap::new(StorageKey::BlankBallots),
+            vote_change_allowed: LookupSet::new(StorageKey::VoteChangeAllowed),
+            post_finish_authority: None,
+            policy_accept_count: LookupMap::new(StorageKey::PolicyAcceptCount),
+            policy_epoch: 0,
+            accepted_policy_epoch: LookupMap::new(StorageKey::AcceptedPolicyEpoch),
         }
     }
 
@@ -91,8 +137,10 @@ impl Contract {
      **********/
 
     /// Creates a new empty proposal. `start` and `end`are timestamps in milliseconds.
-    /// Returns the new proposal ID.
+    /// Returns the new proposal ID or `CreateProposalError` if the proposal parameters are
+    /// not valid.
     /// NOTE: storage is paid from the account state
+    #[handle_result]
     pub fn create_proposal(
         &mut self,
         typ: ProposalType,
@@ -104,34 +152,36 @@ impl Contract {
         seats: u16,
         #[allow(unused_mut)] mut candidates: Vec<AccountId>,
         min_candidate_support: u64,
-    ) -> u32 {
+    ) -> Result<u32, CreateProposalError> {
         self.assert_admin();
         let candidates_len = candidates.len();
-        require!(
-            env::block_timestamp_ms() < start,
-            "proposal start must be in the future"
-        );
-        require!(start < end, "proposal start must be before end");
-        require!(
-            0 < seats && seats <= candidates_len as u16,
-            "require 0 < seats <= candidates.length"
-        );
-        require!(
-            MIN_REF_LINK_LEN <= ref_link.len() && ref_link.len() <= MAX_REF_LINK_LEN,
-            format!(
-                "ref_link length must be between {} and {} bytes",
-                MIN_REF_LINK_LEN, MAX_REF_LINK_LEN
-            )
-        );
+        let now = env::block_timestamp_ms();
+        if start <= now {
+            return Err(CreateProposalError::StartInPast);
+        }
+        if start < now + self.min_start_lead_ms {
+            return Err(CreateProposalError::StartTooSoon(self.min_start_lead_ms));
+        }
+        if end <= start {
+            return Err(CreateProposalError::EndBeforeStart);
+        }
+        if seats == 0 || seats as usize > candidates_len {
+            return Err(CreateProposalError::WrongSeats);
+        }
+        if ref_link.len() < MIN_REF_LINK_LEN || ref_link.len() > MAX_REF_LINK_LEN {
+            return Err(CreateProposalError::RefLinkLength);
+        }
 
         if typ == ProposalType::SetupPackage {
-            validate_setup_package(seats, &candidates);
+            validate_setup_package(seats, &candidates)?;
         }
 
         candidates.sort();
         let mut c1 = &candidates[0];
         for c in candidates.iter().skip(1) {
-            require!(c1 != c, "duplicated candidates");
+            if c1 == c {
+                return Err(CreateProposalError::DuplicatedCandidates);
+            }
             c1 = c;
         }
 
@@ -154,10 +204,12 @@ impl Contract {
 
         self.finish_time = max(self.finish_time, end + cooldown);
         self.proposals.insert(&self.prop_counter, &p);
-        self.prop_counter
+        Ok(self.prop_counter)
     }
 
     /// Transaction to record the predecessor account accepting the Fair Voting Policy.
+    /// The acceptance is valid only for the current policy epoch: after `admin_set_policy`
+    /// the policy must be accepted again.
     /// * `policy` is a blake2s-256 hex-encoded hash (must be 64 bytes) of the Fair Voting Policy text.
     #[payable]
     pub fn accept_fair_voting_policy(&mut self, policy: String) {
@@ -169,40 +221,92 @@ impl Contract {
             )
         );
         let policy = assert_hash_hex_string(&policy);
-        self.accepted_policy
-            .insert(&env::predecessor_account_id(), &policy);
+        let user = env::predecessor_account_id();
+        let prev_epoch = self.accepted_policy_epoch.insert(&user, &self.policy_epoch);
+        let old = self.accepted_policy.insert(&user, &policy);
+        // acceptances from previous epochs are not counted anymore
+        match old.filter(|_| prev_epoch == Some(self.policy_epoch)) {
+            None => {
+                self.policy_count += 1;
+                self.update_policy_accept_count(&policy, true);
+            }
+            Some(old) if old != policy => {
+                self.update_policy_accept_count(&old, false);
+                self.update_policy_accept_count(&policy, true);
+            }
+            Some(_) => (),
+        }
     }
 
     /// Election vote using a seat-selection mechanism.
     /// For the `SetupPackage` proposal, vote must be an empty list.
+    /// The voter IAH SBT is locked (soul transfers are blocked) until the proposal end, so the
+    /// SBT can't be moved to another account and used to vote again.
+    /// Must be called via `iah_registry.is_human_call_lock` with
+    /// `lock_duration: proposal.end - now + 1`.
+    /// Panics if the caller is not the registry or the SBT lock doesn't cover the proposal end.
     // NOTE: we don't need to take storage deposit because user is required to bond at least
     // 3N, that will way more than what's needed to vote for few proposals.
-    pub fn vote(&mut self, prop_id: u32, vote: Vote) -> Promise {
-        let user = env::predecessor_account_id();
-        let p = self._proposal(prop_id);
-        p.assert_active();
+    pub fn vote(
+        &mut self,
+        caller: AccountId,
+        locked_until: u64,
+        #[allow(unused_variables)] iah_proof: Option<HumanSBTs>,
+        payload: VotePayload,
+    ) -> Promise {
+        self.assert_registry();
+        let p = self._proposal(payload.prop_id);
         require!(
-            env::prepaid_gas() >= VOTE_GAS,
-            format!("not enough gas, min: {:?}", VOTE_GAS)
+            locked_until > p.end,
+            "IAH SBT must be locked until the proposal end"
+        );
+        self.vote_impl(caller, payload.prop_id, p, payload.vote)
+    }
+
+    /// Votes on multiple proposals in a single transaction. The voter SBT and account flag are
+    /// verified once, and all votes are recorded in the callback. Each vote is validated in the
+    /// same way as in `vote`. If any of the votes fails in the callback, none of them is
+    /// recorded.
+    /// Must be called via `iah_registry.is_human_call_lock`, with the lock covering the end of
+    /// all voted proposals. `payload` is the list of `(prop_id, vote)` pairs.
+    /// Requires `VOTE_GAS` plus `VOTE_GAS_CALLBACK` for each additional vote.
+    pub fn vote_many(
+        &mut self,
+        caller: AccountId,
+        locked_until: u64,
+        #[allow(unused_variables)] iah_proof: Option<HumanSBTs>,
+        payload: Vec<(u32, Vote)>,
+    ) -> Promise {
+        self.assert_registry();
+        require!(!payload.is_empty(), "no votes");
+        for (prop_id, vote) in &payload {
+            let p = self._proposal(*prop_id);
+            require!(
+                locked_until > p.end,
+                "IAH SBT must be locked until the proposal end"
+            );
+            self.assert_can_vote(*prop_id, &p, vote);
+        }
+        let n = payload.len() as u64;
+        let min_gas = Gas(VOTE_GAS.0 + VOTE_GAS_CALLBACK.0 * (n - 1));
+        require!(
+            env::prepaid_gas() >= min_gas,
+            format!("not enough gas, min: {:?}", min_gas)
         );
         require!(
-            self.policy == self.accepted_policy.get(&user).unwrap_or_default(),
+            self.has_accepted_policy(&caller),
             "user didn't accept the voting policy, or the accepted voting policy doesn't match the required one"
         );
 
-        validate_vote(p.typ, &vote, p.seats, &p.candidates);
-        // call SBT registry to verify SBT
-        let sbt_promise = ext_sbtreg::ext(self.sbt_registry.clone()).is_human(user.clone());
-        let acc_flag = ext_sbtreg::ext(self.sbt_registry.clone()).account_flagged(user.clone());
-
-        sbt_promise.and(acc_flag).then(
+        self.verify_voter(&caller).then(
             ext_self::ext(env::current_account_id())
-                .with_static_gas(VOTE_GAS_CALLBACK)
-                .on_vote_verified(prop_id, user, vote),
+                .with_static_gas(Gas(VOTE_GAS_CALLBACK.0 * n))
+                .on_vote_many_verified(caller, payload),
         )
     }
 
     /// Allows user to bond before voting. The method needs to be called through registry.is_human_call
+    /// Subsequent bond calls accumulate: the attached deposit is added to the existing bond.
     /// Panics if the caller is not registry
     /// Emits bond event
     #[payable]
@@ -230,9 +334,50 @@ impl Contract {
             );
         }
 
-        emit_bond(deposit);
-        let amount = self.bonded_amounts.get(&token_id).unwrap_or(0);
-        self.bonded_amounts.insert(&token_id, &(deposit + amount));
+        self.bond_impl(token_id, deposit);
+        PromiseOrValue::Value(U128(deposit))
+    }
+
+    /// Allows user who already unbonded to bond again, when the elections were reopened
+    /// (`finish_time` was extended). Votes are removed during unbond, so the user has to vote
+    /// again after rebonding. The fair voting policy must also be accepted again.
+    /// The method needs to be called through registry.is_human_call.
+    /// Fails if the caller is not registry, after `finish_time` or if the user is still bonded
+    /// (use `bond` to top up an existing bond).
+    /// Emits bond event
+    #[payable]
+    pub fn rebond(
+        &mut self,
+        caller: AccountId,
+        iah_proof: HumanSBTs,
+        #[allow(unused_variables)] payload: serde_json::Value, // required by is_human_call
+    ) -> PromiseOrValue<U128> {
+        let deposit = env::attached_deposit();
+        if env::predecessor_account_id() != self.sbt_registry {
+            return PromiseOrValue::Promise(
+                Promise::new(caller)
+                    .transfer(deposit)
+                    .then(Self::fail("can only be called by registry")),
+            );
+        }
+
+        let (ok, token_id) = Self::is_human_issuer(&iah_proof);
+        let err = if !ok {
+            Some("not a human")
+        } else if env::block_timestamp_ms() > self.finish_time {
+            Some("cannot rebond: election is over")
+        } else if self.bonded_amounts.contains_key(&token_id) {
+            Some("already bonded, use bond to increase the bond")
+        } else {
+            None
+        };
+        if let Some(err) = err {
+            return PromiseOrValue::Promise(
+                Promise::new(caller).transfer(deposit).then(Self::fail(err)),
+            );
+        }
+
+        self.bond_impl(token_id, deposit);
         PromiseOrValue::Value(U128(deposit))
     }
 
@@ -241,6 +386,8 @@ impl Contract {
     /// Panics if the `predecessor_account_id` is not registry
     /// Panics if called before the elections is over
     /// Panics if user didn't bond
+    /// NOTE: unbond removes all user votes. If the elections are reopened (`finish_time` is
+    /// extended), the user must `rebond` and vote again.
     #[payable]
     #[allow(unused_variables)] // `payload` is not used but it needs to be payload so that is_human_call works
     pub fn unbond(
@@ -268,40 +415,57 @@ impl Contract {
             let proposal = self.proposals.get(&i);
             if let Some(mut prop) = proposal {
                 prop.user_sbt.remove(&caller);
-                if prop.voters.remove(&token_id).is_none() {
+                // cancelled proposals can't be voted on, so they are not required for the I Voted SBT
+                if prop.voters.remove(&token_id).is_none() && !self.cancelled_proposals.contains(&i)
+                {
                     voted_for_all = false;
                 }
             }
         }
-        self.accepted_policy.remove(&caller);
+        // counters are saturated, because they are not set for records created before the migration
+        let epoch = self.accepted_policy_epoch.remove(&caller);
+        if let Some(policy) = self.accepted_policy.remove(&caller) {
+            if epoch == Some(self.policy_epoch) {
+                self.policy_count = self.policy_count.saturating_sub(1);
+                self.update_policy_accept_count(&policy, false);
+            }
+        }
 
-        let mut unbond_amount = self
+        let unbond_amount = self
             .bonded_amounts
             .remove(&token_id)
             .expect("voter didn't bond");
+        self.bonded_count = self.bonded_count.saturating_sub(1);
 
         // call to registry to mint `I Voted` SBT
         if voted_for_all {
-            unbond_amount -= MINT_COST;
-            // No need to "chain" NEAR transfer.
-            Promise::new(caller.clone()).transfer(unbond_amount);
-            ext_sbtreg::ext(self.sbt_registry.clone())
-                .with_static_gas(MINT_GAS)
-                .with_attached_deposit(MINT_COST)
-                .sbt_mint(vec![(
-                    caller,
-                    vec![TokenMetadata {
-                        class: I_VOTED_SBT_CLASS,
-                        issued_at: Some(env::block_timestamp_ms()),
-                        expires_at: None,
-                        reference: None,
-                        reference_hash: None,
-                    }],
-                )])
+            // the bond could have been partially slashed, so it may not cover the mint cost.
+            match unbond_amount.checked_sub(MINT_COST) {
+                Some(remaining) => {
+                    // No need to "chain" NEAR transfer.
+                    Promise::new(caller.clone()).transfer(remaining);
+                    return ext_sbtreg::ext(self.sbt_registry.clone())
+                        .with_static_gas(MINT_GAS)
+                        .with_attached_deposit(MINT_COST)
+                        .sbt_mint(vec![(
+                            caller,
+                            vec![TokenMetadata {
+                                class: I_VOTED_SBT_CLASS,
+                                issued_at: Some(env::block_timestamp_ms()),
+                                expires_at: None,
+                                reference: None,
+                                reference_hash: None,
+                            }],
+                        )]);
+                }
+                None => {
+                    env::log_str("Bond doesn't cover the mint cost. Skipping I Voted SBT mint.")
+                }
+            }
         } else {
             env::log_str("Didn't vote for all proposals. Skipping I Voted SBT mint.");
-            Promise::new(caller.clone()).transfer(unbond_amount)
         }
+        Promise::new(caller).transfer(unbond_amount)
     }
 
     /// Method for the authority to revoke any votes
@@ -323,13 +487,36 @@ impl Contract {
 
         let mut p = self._proposal(prop_id);
         for t in token_ids {
-            p.revoke_votes(t)?;
+            self.revoke_token_votes(prop_id, &mut p, t)?;
         }
         self.proposals.insert(&prop_id, &p);
         emit_revoke_vote(prop_id);
         Ok(())
     }
 
+    /// Allows admin to slash part of the bond of the given SBT. The slashed `amount` is tracked
+    /// in `total_slashed`, the remaining bond stays with the token and is returned on `unbond`.
+    /// Same as `admin_revoke_vote`, can only be called before the `finish_time` (after that
+    /// users can unbond).
+    /// Panics if the token didn't bond or the `amount` is zero or bigger than the bond.
+    pub fn slash_bond_partial(&mut self, token_id: TokenId, amount: U128) {
+        self.assert_admin();
+        require!(
+            env::block_timestamp_ms() <= self.finish_time,
+            "can only slash before the elections finish time"
+        );
+        let bond = self
+            .bonded_amounts
+            .get(&token_id)
+            .expect("token didn't bond");
+        require!(
+            amount.0 > 0 && amount.0 <= bond,
+            "amount must be positive and not bigger than the bond"
+        );
+        self.bonded_amounts.insert(&token_id, &(bond - amount.0));
+        self.total_slashed += amount.0;
+    }
+
     /// Method to revoke votes from blacklisted accounts.
     /// The method makes a call to the registry to verify the user is blacklisted.
     /// Panics if:
@@ -347,6 +534,17 @@ impl Contract {
             )
     }
 
+    /// Allows admin to update the Fair Voting Policy. Increases the policy epoch, so all
+    /// existing acceptances become stale (even if the new policy hash is the same) and users
+    /// must accept the policy again before voting. The policy acceptance counters are reset.
+    /// * `policy` is a blake2s-256 hex-encoded hash of the Fair Voting Policy text.
+    pub fn admin_set_policy(&mut self, policy: String) {
+        self.assert_admin();
+        self.policy = assert_hash_hex_string(&policy);
+        self.policy_epoch += 1;
+        self.policy_count = 0;
+    }
+
     /// Allows admin to overwrite the existing finish time by extending the cooldown.
     /// Must be a Unix Time in miliseconds.
     pub fn admin_set_finish_time(&mut self, finish_time: u64) {
@@ -358,19 +556,93 @@ impl Contract {
         self.finish_time = finish_time;
     }
 
-    /// Allows admin to disqualify candidates.
+    /// Allows the authority to set an account which can call the cleanup methods (currently
+    /// `admin_mint_sbt`) once the elections are finished (after the `finish_time`). Other admin
+    /// methods remain restricted to the `authority`.
+    /// `None` removes the post finish authority.
+    pub fn admin_set_post_finish_authority(&mut self, acc: Option<AccountId>) {
+        self.assert_admin();
+        self.post_finish_authority = acc;
+    }
+
+    /// Allows admin to disqualify candidates. The list replaces the existing list of
+    /// disqualified candidates (use `admin_requalify_candidate` to remove a single candidate).
+    /// Duplicates are skipped. Emits `candidates_disqualified` event with the new list.
     pub fn admin_disqualify_candidates(&mut self, candidates: Vec<AccountId>) {
         self.assert_admin();
-        let mut to_disqualify: HashSet<AccountId> = HashSet::new();
-        for c in candidates.iter() {
-            to_disqualify.insert(c.clone());
+        let mut disqualified = HashSet::new();
+        let accounts: Vec<AccountId> = candidates
+            .into_iter()
+            .filter(|c| disqualified.insert(c.clone()))
+            .collect();
+        self.disqualified_candidates.set(&disqualified);
+        emit_candidates_disqualified(&accounts);
+    }
+
+    /// Allows admin to reverse a candidate disqualification.
+    /// Returns false if the candidate was not disqualified.
+    pub fn admin_requalify_candidate(&mut self, candidate: AccountId) -> bool {
+        self.assert_admin();
+        let mut disqualified = self.disqualified_candidates.get().unwrap_or_default();
+        if !disqualified.remove(&candidate) {
+            return false;
+        }
+        self.disqualified_candidates.set(&disqualified);
+        emit_candidate_requalified(&candidate);
+        true
+    }
+
+    /// Allows admin to cancel a proposal, eg when all candidates were disqualified or the
+    /// proposal is otherwise invalid. Cancelled proposals can't be voted on.
+    /// Panics if the proposal doesn't exist or it has already ended.
+    pub fn admin_cancel_proposal(&mut self, prop_id: u32) {
+        self.assert_admin();
+        let p = self._proposal(prop_id);
+        require!(
+            !matches!(
+                p.status(env::block_timestamp_ms(), self.finish_time),
+                ProposalStatus::ENDED
+            ),
+            "can't cancel an ended proposal"
+        );
+        self.cancelled_proposals.insert(&prop_id);
+    }
+
+    /// Allows admin to update the proposal quorum. Can only be called before the proposal starts.
+    /// Panics if the proposal doesn't exist or it has already started.
+    pub fn admin_set_quorum(&mut self, prop_id: u32, quorum: u32) {
+        self.assert_admin();
+        let mut p = self._proposal(prop_id);
+        require!(
+            env::block_timestamp_ms() < p.start,
+            "can't update quorum of a started proposal"
+        );
+        p.quorum = quorum;
+        self.proposals.insert(&prop_id, &p);
+    }
+
+    /// Allows admin to enable or disable vote changes for the proposal. When enabled, a voter
+    /// can vote again before the proposal end: the previous vote is replaced by the new one.
+    /// Can only be called before the proposal starts.
+    /// Panics if the proposal doesn't exist or it has already started.
+    pub fn admin_allow_vote_change(&mut self, prop_id: u32, allow: bool) {
+        self.assert_admin();
+        let p = self._proposal(prop_id);
+        require!(
+            env::block_timestamp_ms() < p.start,
+            "can't update vote change of a started proposal"
+        );
+        if allow {
+            self.vote_change_allowed.insert(&prop_id);
+        } else {
+            self.vote_change_allowed.remove(&prop_id);
         }
-        self.disqualified_candidates.set(&to_disqualify);
     }
 
     /// Allows admin to mint SBT to the given list of accounts.
+    /// Can also be called by the `post_finish_authority` after the `finish_time`.
     pub fn admin_mint_sbt(&mut self, recipients: Vec<AccountId>, class: ClassId) {
-        self.assert_admin();
+        self.assert_post_finish_authority();
         let now = env::block_timestamp_ms();
         let len = recipients.len();
         let token_spec = recipients
@@ -415,29 +687,25 @@ impl Contract {
         voter: AccountId,
         vote: Vote,
     ) -> Result<(), VoteError> {
-        let (ok, token_id) = Self::is_human_issuer(&iah_proof);
-        if !ok {
-            return Err(VoteError::NoSBTs);
-        }
-
-        let required_bond = match account_flag {
-            Some(AccountFlag::Blacklisted) => return Err(VoteError::Blacklisted),
-            Some(AccountFlag::Verified) => BOND_AMOUNT,
-            None => GRAY_BOND_AMOUNT,
-        };
+        let token_id = self.voter_token(&iah_proof, &account_flag)?;
+        self.record_vote(token_id, prop_id, voter, vote)
+    }
 
-        if let Some(bond) = self.bonded_amounts.get(&token_id) {
-            if bond < required_bond {
-                return Err(VoteError::MinBond(required_bond, bond));
-            }
-        } else {
-            return Err(VoteError::NoBond);
+    /// Callback of `vote_many`: the voter is verified once and then all votes are recorded.
+    /// If any of the votes fails, none of them is recorded.
+    #[private]
+    #[handle_result]
+    pub fn on_vote_many_verified(
+        &mut self,
+        #[callback_unwrap] iah_proof: HumanSBTs,
+        #[callback_unwrap] account_flag: Option<AccountFlag>,
+        voter: AccountId,
+        votes: Vec<(u32, Vote)>,
+    ) -> Result<(), VoteError> {
+        let token_id = self.voter_token(&iah_proof, &account_flag)?;
+        for (prop_id, vote) in votes {
+            self.record_vote(token_id, prop_id, voter.clone(), vote)?;
         }
-
-        let mut p = self._proposal(prop_id);
-        p.vote_on_verified(&iah_proof[0].1, voter, vote)?;
-        self.proposals.insert(&prop_id, &p);
-        emit_vote(prop_id);
         Ok(())
     }
 
@@ -459,8 +727,7 @@ impl Contract {
         }
         let mut p = self._proposal(prop_id);
         let token_id = p.user_sbt.get(&user).ok_or(RevokeVoteError::NotVoted)?;
-
-        p.revoke_votes(token_id)?;
+        self.revoke_token_votes(prop_id, &mut p, token_id)?;
         self.proposals.insert(&prop_id, &p);
         emit_revoke_vote(prop_id);
         Ok(())
@@ -477,21 +744,159 @@ impl Contract {
     //     }
     // }
 
+    /// Revokes the token votes in the proposal `p` and updates the `blank_ballots` counter
+    /// if the revoked vote was a blank ballot. The caller must save the proposal.
+    fn revoke_token_votes(
+        &mut self,
+        prop_id: u32,
+        p: &mut Proposal,
+        token_id: TokenId,
+    ) -> Result<(), RevokeVoteError> {
+        let blank = p.voters.get(&token_id).map_or(false, |v| v.is_empty());
+        p.revoke_votes(token_id)?;
+        if blank {
+            // blank ballots cast before the migration are not counted
+            let n = self.blank_ballots.get(&prop_id).unwrap_or(0);
+            self.blank_ballots.insert(&prop_id, &n.saturating_sub(1));
+        }
+        Ok(())
+    }
+
     fn fail(reason: &str) -> Promise {
         Self::ext(env::current_account_id())
             .with_static_gas(FAILURE_CALLBACK_GAS)
             .on_failure(reason.to_string())
     }
 
+    /// Records the bond and emits bond event. The bonded counter is only increased for new bonds.
+    fn bond_impl(&mut self, token_id: TokenId, deposit: Balance) {
+        emit_bond(deposit);
+        let amount = match self.bonded_amounts.get(&token_id) {
+            Some(amount) => amount,
+            None => {
+                self.bonded_count += 1;
+                0
+            }
+        };
+        self.bonded_amounts.insert(&token_id, &(deposit + amount));
+    }
+
     #[inline]
     fn is_human_issuer(iah_proof: &HumanSBTs) -> (bool, TokenId) {
         // in current version we support only one proof of personhood issuer: Fractal, so here
         // we simplify by requiring that the result contains tokens only from one issuer.
-        if iah_proof.is_empty() || !(iah_proof.len() == 1 && iah_proof[0].1.len() == 1) {
-            (false, 0)
+        // An account can hold multiple tokens from that issuer, in such case we use the first one.
+        match iah_proof.as_slice() {
+            [(_, tokens)] if !tokens.is_empty() => (true, tokens[0]),
+            _ => (false, 0),
+        }
+    }
+
+    /// Increases or decreases the number of accounts which accepted the `policy` in the current
+    /// policy epoch.
+    // the counter is saturated, because it's not set for acceptances before the migration
+    fn update_policy_accept_count(&mut self, policy: &[u8; 32], increase: bool) {
+        let key = (self.policy_epoch, *policy);
+        let count = self.policy_accept_count.get(&key).unwrap_or_default();
+        let count = if increase {
+            count + 1
+        } else {
+            count.saturating_sub(1)
+        };
+        if count == 0 {
+            self.policy_accept_count.remove(&key);
+        } else {
+            self.policy_accept_count.insert(&key, &count);
+        }
+    }
+
+    /// Returns true if the user accepted the current policy in the current policy epoch.
+    // acceptances recorded before the migration have no epoch, they are valid in the epoch 0
+    fn has_accepted_policy(&self, user: &AccountId) -> bool {
+        self.policy == self.accepted_policy.get(user).unwrap_or_default()
+            && self.policy_epoch == self.accepted_policy_epoch.get(user).unwrap_or_default()
+    }
+
+    fn vote_impl(&self, user: AccountId, prop_id: u32, p: Proposal, vote: Vote) -> Promise {
+        self.assert_can_vote(prop_id, &p, &vote);
+        require!(
+            env::prepaid_gas() >= VOTE_GAS,
+            format!("not enough gas, min: {:?}", VOTE_GAS)
+        );
+        require!(
+            self.has_accepted_policy(&user),
+            "user didn't accept the voting policy, or the accepted voting policy doesn't match the required one"
+        );
+
+        self.verify_voter(&user).then(
+            ext_self::ext(env::current_account_id())
+                .with_static_gas(VOTE_GAS_CALLBACK)
+                .on_vote_verified(prop_id, user, vote),
+        )
+    }
+
+    /// Checks that the proposal is active and not cancelled, and that the vote is valid.
+    fn assert_can_vote(&self, prop_id: u32, p: &Proposal, vote: &Vote) {
+        p.assert_active();
+        require!(
+            !self.cancelled_proposals.contains(&prop_id),
+            "proposal is cancelled"
+        );
+        validate_vote(p.typ, vote, p.seats, &p.candidates);
+    }
+
+    /// Calls the SBT registry to get the user IAH SBTs and the account flag.
+    fn verify_voter(&self, user: &AccountId) -> Promise {
+        let sbt_promise = ext_sbtreg::ext(self.sbt_registry.clone()).is_human(user.clone());
+        let acc_flag = ext_sbtreg::ext(self.sbt_registry.clone()).account_flagged(user.clone());
+        sbt_promise.and(acc_flag)
+    }
+
+    /// Returns the voter IAH token id if the voter is human and bonded enough.
+    fn voter_token(
+        &self,
+        iah_proof: &HumanSBTs,
+        account_flag: &Option<AccountFlag>,
+    ) -> Result<TokenId, VoteError> {
+        let (ok, token_id) = Self::is_human_issuer(iah_proof);
+        if !ok {
+            return Err(VoteError::NoSBTs);
+        }
+
+        let required_bond = bond_for_flag(account_flag)?;
+
+        if let Some(bond) = self.bonded_amounts.get(&token_id) {
+            if bond < required_bond {
+                return Err(VoteError::MinBond(required_bond, bond));
+            }
         } else {
-            (true, *iah_proof[0].1.first().unwrap())
+            return Err(VoteError::NoBond);
+        }
+        Ok(token_id)
+    }
+
+    /// Records a verified vote and emits the vote event.
+    fn record_vote(
+        &mut self,
+        token_id: TokenId,
+        prop_id: u32,
+        voter: AccountId,
+        vote: Vote,
+    ) -> Result<(), VoteError> {
+        let blank = vote.is_empty();
+        let allow_change = self.vote_change_allowed.contains(&prop_id);
+        let mut p = self._proposal(prop_id);
+        let previous = p.vote_on_verified(&vec![token_id], voter, vote, allow_change)?;
+        self.proposals.insert(&prop_id, &p);
+        let was_blank = previous.map_or(false, |v| v.is_empty());
+        if blank != was_blank {
+            let n = self.blank_ballots.get(&prop_id).unwrap_or(0);
+            // blank ballots cast before the migration are not counted
+            let n = if blank { n + 1 } else { n.saturating_sub(1) };
+            self.blank_ballots.insert(&prop_id, &n);
         }
+        emit_vote(prop_id);
+        Ok(())
     }
 
     #[inline]
@@ -501,18 +906,46 @@ impl Contract {
             "not an admin"
         );
     }
+
+    /// Checks that the caller is the `authority`, or the `post_finish_authority` once the
+    /// elections are finished. Must only be used by the cleanup methods.
+    fn assert_post_finish_authority(&self) {
+        let caller = env::predecessor_account_id();
+        let post_finish = env::block_timestamp_ms() > self.finish_time
+            && self.post_finish_authority.as_ref() == Some(&caller);
+        require!(self.authority == caller || post_finish, "not an admin");
+    }
+
+    fn assert_registry(&self) {
+        require!(
+            env::predecessor_account_id() == self.sbt_registry,
+            "must be called by the registry"
+        );
+    }
+}
+
+/// Returns the bond amount required to vote by an account with the given IAH registry flag.
+fn bond_for_flag(flag: &Option<AccountFlag>) -> Result<Balance, VoteError> {
+    match flag {
+        Some(AccountFlag::Blacklisted) => Err(VoteError::Blacklisted),
+        Some(AccountFlag::Verified) => Ok(BOND_AMOUNT),
+        None => Ok(GRAY_BOND_AMOUNT),
+    }
 }
 
-fn validate_setup_package(seats: u16, cs: &Vec<AccountId>) {
+fn validate_setup_package(seats: u16, cs: &Vec<AccountId>) -> Result<(), CreateProposalError> {
     // Users can vote to at most one option
-    require!(seats == 1, "SetupPackage seats must equal 1");
-    require!(
-        cs.len() == 3
-            && cs[0].as_str() == "yes"
-            && cs[1].as_str() == "no"
-            && cs[2].as_str() == "abstain",
-        "SetupPackage candidates must be ['yes', 'no', 'abstain']"
-    );
+    if seats != 1 {
+        return Err(CreateProposalError::SetupPackageSeats);
+    }
+    if !(cs.len() == 3
+        && cs[0].as_str() == "yes"
+        && cs[1].as_str() == "no"
+        && cs[2].as_str() == "abstain")
+    {
+        return Err(CreateProposalError::SetupPackageCandidates);
+    }
+    Ok(())
 }
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
@@ -613,17 +1046,19 @@ mod unit_tests {
         ctx.block_timestamp = (START) * MSECOND;
         testing_env!(ctx.clone());
 
-        let prop_id = ctr.create_proposal(
-            crate::ProposalType::HouseOfMerit,
-            START + 1,
-            START + 10,
-            100,
-            String::from("ref_link.io"),
-            10,
-            seats,
-            candidates,
-            min_candidate_support,
-        );
+        let prop_id = ctr
+            .create_proposal(
+                crate::ProposalType::HouseOfMerit,
+                START + 1,
+                START + 10,
+                100,
+                String::from("ref_link.io"),
+                10,
+                seats,
+                candidates,
+                min_candidate_support,
+            )
+            .unwrap();
         ctx.block_timestamp = (START + 2) * MSECOND;
         testing_env!(ctx.clone());
 
@@ -669,6 +1104,7 @@ mod unit_tests {
             vec![candidate(1), candidate(2), candidate(3)],
             2,
         )
+        .unwrap()
     }
 
     fn mk_proposal_setup_package(ctr: &mut Contract) -> u32 {
@@ -683,6 +1119,7 @@ mod unit_tests {
             setup_package_candidates(),
             2,
         )
+        .unwrap()
     }
 
     fn mk_human_sbt(sbt: TokenId) -> HumanSBTs {
@@ -721,14 +1158,46 @@ mod unit_tests {
         testing_env!(ctx.clone());
     }
 
-    fn setup(predecessor: &AccountId) -> (VMContext, Contract) {
-        let mut ctx = VMContextBuilder::new()
-            .predecessor_account_id(admin())
-            .block_timestamp(START * MSECOND)
-            .is_view(false)
-            .build();
+    /// Calls `vote` through the registry (`is_human_call_lock`) on behalf of the context
+    /// predecessor, with the IAH SBT locked until the proposal end.
+    fn vote_call(ctx: &mut VMContext, ctr: &mut Contract, prop_id: u32, vote: Vote) {
+        let caller = ctx.predecessor_account_id.clone();
+        let locked_until = ctr._proposal(prop_id).end + 1;
+        ctx.predecessor_account_id = sbt_registry();
+        testing_env!(ctx.clone());
+        ctr.vote(
+            caller.clone(),
+            locked_until,
+            None,
+            VotePayload { prop_id, vote },
+        );
+        ctx.predecessor_account_id = caller;
+        testing_env!(ctx.clone());
+    }
+
+    /// Same as `vote_call`, but for `vote_many`.
+    fn vote_many_call(ctx: &mut VMContext, ctr: &mut Contract, votes: Vec<(u32, Vote)>) {
+        let caller = ctx.predecessor_account_id.clone();
+        let locked_until = votes
+            .iter()
+            .map(|(prop_id, _)| ctr._proposal(*prop_id).end + 1)
+            .max()
+            .unwrap_or_default();
+        ctx.predecessor_account_id = sbt_registry();
+        testing_env!(ctx.clone());
+        ctr.vote_many(caller.clone(), locked_until, None, votes);
+        ctx.predecessor_account_id = caller;
+        testing_env!(ctx.clone());
+    }
+
+    fn setup(predecessor: &AccountId) -> (VMContext, Contract) {
+        let mut ctx = VMContextBuilder::new()
+            .predecessor_account_id(admin())
+            .block_timestamp(START * MSECOND)
+            .is_view(false)
+            .build();
         testing_env!(ctx.clone());
-        let ctr = Contract::new(admin(), sbt_registry(), policy1(), START + 100);
+        let ctr = Contract::new(admin(), sbt_registry(), policy1(), START + 100, 0);
         ctx.predecessor_account_id = predecessor.clone();
         testing_env!(ctx.clone());
         (ctx, ctr)
@@ -748,201 +1217,350 @@ mod unit_tests {
     }
 
     #[test]
-    #[should_panic(expected = "proposal start must be in the future")]
+    fn post_finish_authority() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        ctr.admin_set_post_finish_authority(Some(bob()));
+        assert_eq!(ctr.post_finish_authority(), Some(bob()));
+
+        // the authority can call the cleanup methods at any time
+        ctr.assert_post_finish_authority();
+        ctx.block_timestamp = (ctr.finish_time + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        ctr.assert_post_finish_authority();
+
+        // post finish authority is accepted after the finish time
+        ctx.predecessor_account_id = bob();
+        testing_env!(ctx.clone());
+        ctr.assert_post_finish_authority();
+        ctr.admin_mint_sbt(vec![alice()], I_VOTED_SBT_CLASS);
+
+        // post finish authority can be removed
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx.clone());
+        ctr.admin_set_post_finish_authority(None);
+        assert_eq!(ctr.post_finish_authority(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "not an admin")]
+    fn post_finish_authority_removed() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        ctr.admin_set_post_finish_authority(Some(bob()));
+        ctr.admin_set_post_finish_authority(None);
+        ctx.block_timestamp = (ctr.finish_time + 1) * MSECOND;
+        ctx.predecessor_account_id = bob();
+        testing_env!(ctx);
+        ctr.assert_post_finish_authority();
+    }
+
+    #[test]
+    #[should_panic(expected = "not an admin")]
+    fn post_finish_authority_before_finish() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        ctr.admin_set_post_finish_authority(Some(bob()));
+        ctx.block_timestamp = ctr.finish_time * MSECOND;
+        ctx.predecessor_account_id = bob();
+        testing_env!(ctx);
+        ctr.assert_post_finish_authority();
+    }
+
+    #[test]
+    #[should_panic(expected = "not an admin")]
+    fn post_finish_authority_not_admin() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        ctr.admin_set_post_finish_authority(Some(bob()));
+        ctx.block_timestamp = (ctr.finish_time + 1) * MSECOND;
+        ctx.predecessor_account_id = bob();
+        testing_env!(ctx);
+        // can't reopen the elections
+        ctr.admin_set_finish_time(ctr.finish_time + 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "not an admin")]
+    fn post_finish_authority_can_not_set_itself() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        ctr.admin_set_post_finish_authority(Some(bob()));
+        ctx.block_timestamp = (ctr.finish_time + 1) * MSECOND;
+        ctx.predecessor_account_id = bob();
+        testing_env!(ctx);
+        ctr.admin_set_post_finish_authority(Some(alice()));
+    }
+
+    #[test]
+    #[should_panic(expected = "not an admin")]
+    fn set_post_finish_authority_not_admin() {
+        let (_, mut ctr) = setup(&alice());
+        ctr.admin_set_post_finish_authority(Some(bob()));
+    }
+
+    #[test]
+    fn create_proposal_min_start_lead() {
+        let (_, mut ctr) = setup(&admin());
+        ctr.min_start_lead_ms = 10;
+        let mk = |ctr: &mut Contract, start: u64| {
+            ctr.create_proposal(
+                crate::ProposalType::HouseOfMerit,
+                start,
+                START + 100,
+                100,
+                String::from("ref_link.io"),
+                2,
+                2,
+                vec![candidate(1), candidate(2)],
+                1,
+            )
+            .unwrap()
+        };
+        assert_eq!(mk(&mut ctr, START + 10), 1);
+        assert_eq!(mk(&mut ctr, START + 11), 2);
+    }
+
+    #[test]
+    fn create_proposal_start_too_soon() {
+        let (_, mut ctr) = setup(&admin());
+        ctr.min_start_lead_ms = 10;
+        assert_eq!(
+            ctr.create_proposal(
+                crate::ProposalType::HouseOfMerit,
+                START + 9,
+                START + 100,
+                100,
+                String::from("ref_link.io"),
+                2,
+                2,
+                vec![candidate(1), candidate(2)],
+                1,
+            ),
+            Err(CreateProposalError::StartTooSoon(10))
+        );
+    }
+
+    #[test]
     fn create_proposal_wrong_start_time() {
         let (_, mut ctr) = setup(&admin());
-        ctr.create_proposal(
-            crate::ProposalType::HouseOfMerit,
-            START - 1,
-            START + 100,
-            100,
-            String::from("ref_link.io"),
-            2,
-            2,
-            vec![candidate(1)],
-            2,
+        assert_eq!(
+            ctr.create_proposal(
+                crate::ProposalType::HouseOfMerit,
+                START - 1,
+                START + 100,
+                100,
+                String::from("ref_link.io"),
+                2,
+                2,
+                vec![candidate(1)],
+                2,
+            ),
+            Err(CreateProposalError::StartInPast)
         );
     }
 
     #[test]
-    #[should_panic(expected = "proposal start must be before end")]
     fn create_proposal_end_before_start() {
         let (_, mut ctr) = setup(&admin());
 
-        ctr.create_proposal(
-            crate::ProposalType::HouseOfMerit,
-            START + 10,
-            START,
-            100,
-            String::from("ref_link.io"),
-            2,
-            2,
-            vec![candidate(1)],
-            2,
+        assert_eq!(
+            ctr.create_proposal(
+                crate::ProposalType::HouseOfMerit,
+                START + 10,
+                START,
+                100,
+                String::from("ref_link.io"),
+                2,
+                2,
+                vec![candidate(1)],
+                2,
+            ),
+            Err(CreateProposalError::EndBeforeStart)
         );
     }
 
     #[test]
-    #[should_panic(expected = "ref_link length must be between 6 and 120 bytes")]
     fn create_proposal_wrong_ref_link_length() {
         let (_, mut ctr) = setup(&admin());
 
-        ctr.create_proposal(
-            crate::ProposalType::HouseOfMerit,
-            START + 1,
-            START + 10,
-            100,
-            String::from("short"),
-            2,
-            1,
-            vec![candidate(1)],
-            2,
+        assert_eq!(
+            ctr.create_proposal(
+                crate::ProposalType::HouseOfMerit,
+                START + 1,
+                START + 10,
+                100,
+                String::from("short"),
+                2,
+                1,
+                vec![candidate(1)],
+                2,
+            ),
+            Err(CreateProposalError::RefLinkLength)
         );
     }
 
     #[test]
-    #[should_panic(expected = "duplicated candidates")]
     fn create_proposal_duplicated_candidates() {
         let (_, mut ctr) = setup(&admin());
 
-        ctr.create_proposal(
-            crate::ProposalType::HouseOfMerit,
-            START + 1,
-            START + 10,
-            100,
-            String::from("ref_link.io"),
-            2,
-            2,
-            vec![candidate(1), candidate(1)],
-            2,
+        assert_eq!(
+            ctr.create_proposal(
+                crate::ProposalType::HouseOfMerit,
+                START + 1,
+                START + 10,
+                100,
+                String::from("ref_link.io"),
+                2,
+                2,
+                vec![candidate(1), candidate(1)],
+                2,
+            ),
+            Err(CreateProposalError::DuplicatedCandidates)
         );
     }
 
     #[test]
-    #[should_panic(expected = "require 0 < seats <= candidates.length")]
     fn create_proposal_zero_seats() {
         let (_, mut ctr) = setup(&admin());
-        ctr.create_proposal(
-            crate::ProposalType::HouseOfMerit,
-            START + 1,
-            START + 10,
-            100,
-            String::from("ref_link.io"),
-            2,
-            0,
-            vec![candidate(1), candidate(1)],
-            1,
+        assert_eq!(
+            ctr.create_proposal(
+                crate::ProposalType::HouseOfMerit,
+                START + 1,
+                START + 10,
+                100,
+                String::from("ref_link.io"),
+                2,
+                0,
+                vec![candidate(1), candidate(1)],
+                1,
+            ),
+            Err(CreateProposalError::WrongSeats)
         );
     }
 
     #[test]
-    #[should_panic(expected = "require 0 < seats <= candidates.length")]
     fn create_proposal_not_enough_candidates() {
         let (_, mut ctr) = setup(&admin());
-        ctr.create_proposal(
-            crate::ProposalType::HouseOfMerit,
-            START + 1,
-            START + 10,
-            100,
-            String::from("ref_link.io"),
-            2,
-            3,
-            vec![candidate(1), candidate(1)],
-            1,
+        assert_eq!(
+            ctr.create_proposal(
+                crate::ProposalType::HouseOfMerit,
+                START + 1,
+                START + 10,
+                100,
+                String::from("ref_link.io"),
+                2,
+                3,
+                vec![candidate(1), candidate(1)],
+                1,
+            ),
+            Err(CreateProposalError::WrongSeats)
         );
     }
 
     #[test]
-    #[should_panic(expected = "SetupPackage seats must equal 1")]
     fn create_proposal_setup_package_wrong_seats() {
         let (_, mut ctr) = setup(&admin());
-        ctr.create_proposal(
-            crate::ProposalType::SetupPackage,
-            START + 1,
-            START + 10,
-            100,
-            String::from("ref_link.io"),
-            2,
-            2,
-            setup_package_candidates(),
-            2,
+        assert_eq!(
+            ctr.create_proposal(
+                crate::ProposalType::SetupPackage,
+                START + 1,
+                START + 10,
+                100,
+                String::from("ref_link.io"),
+                2,
+                2,
+                setup_package_candidates(),
+                2,
+            ),
+            Err(CreateProposalError::SetupPackageSeats)
         );
     }
 
     #[test]
-    #[should_panic(expected = "SetupPackage candidates must be ['yes', 'no', 'abstain']")]
     fn create_proposal_setup_package_wrong_candidates() {
         let (_, mut ctr) = setup(&admin());
-        ctr.create_proposal(
-            crate::ProposalType::SetupPackage,
-            START + 1,
-            START + 10,
-            100,
-            String::from("ref_link.io"),
-            2,
-            1,
-            setup_package_candidates()[..=1].to_vec(),
-            2,
+        assert_eq!(
+            ctr.create_proposal(
+                crate::ProposalType::SetupPackage,
+                START + 1,
+                START + 10,
+                100,
+                String::from("ref_link.io"),
+                2,
+                1,
+                setup_package_candidates()[..=1].to_vec(),
+                2,
+            ),
+            Err(CreateProposalError::SetupPackageCandidates)
         );
     }
 
     #[test]
-    #[should_panic(expected = "SetupPackage candidates must be ['yes', 'no', 'abstain']")]
     fn create_proposal_setup_package_wrong_candidates2() {
         let (_, mut ctr) = setup(&admin());
-        ctr.create_proposal(
-            crate::ProposalType::SetupPackage,
-            START + 1,
-            START + 10,
-            100,
-            String::from("ref_link.io"),
-            2,
-            1,
-            vec![candidate(1), candidate(2), candidate(3)],
-            2,
+        assert_eq!(
+            ctr.create_proposal(
+                crate::ProposalType::SetupPackage,
+                START + 1,
+                START + 10,
+                100,
+                String::from("ref_link.io"),
+                2,
+                1,
+                vec![candidate(1), candidate(2), candidate(3)],
+                2,
+            ),
+            Err(CreateProposalError::SetupPackageCandidates)
         );
     }
 
     #[test]
-    #[should_panic(expected = "SetupPackage candidates must be ['yes', 'no', 'abstain']")]
     fn create_proposal_setup_package_wrong_candidates3() {
         let (_, mut ctr) = setup(&admin());
         let mut cs = setup_package_candidates();
         cs.push("no2".to_string().try_into().unwrap());
-        ctr.create_proposal(
-            crate::ProposalType::SetupPackage,
-            START + 1,
-            START + 10,
-            100,
-            String::from("ref_link.io"),
-            2,
-            1,
-            cs,
-            2,
+        assert_eq!(
+            ctr.create_proposal(
+                crate::ProposalType::SetupPackage,
+                START + 1,
+                START + 10,
+                100,
+                String::from("ref_link.io"),
+                2,
+                1,
+                cs,
+                2,
+            ),
+            Err(CreateProposalError::SetupPackageCandidates)
         );
     }
 
     #[test]
-    #[should_panic(expected = "SetupPackage candidates must be ['yes', 'no', 'abstain']")]
     fn create_proposal_setup_package_wrong_candidates_order() {
         let (_, mut ctr) = setup(&admin());
         let mut cs = setup_package_candidates();
         let c = cs[0].clone();
         cs[0] = cs[1].clone();
         cs[1] = c;
-        ctr.create_proposal(
-            crate::ProposalType::SetupPackage,
-            START + 1,
-            START + 10,
-            100,
-            String::from("ref_link.io"),
-            2,
-            1,
-            cs,
-            2,
+        assert_eq!(
+            ctr.create_proposal(
+                crate::ProposalType::SetupPackage,
+                START + 1,
+                START + 10,
+                100,
+                String::from("ref_link.io"),
+                2,
+                1,
+                cs,
+                2,
+            ),
+            Err(CreateProposalError::SetupPackageCandidates)
         );
     }
 
+    #[test]
+    #[should_panic(expected = "proposal start must be at least 10ms after now")]
+    fn create_proposal_error_message() {
+        use near_sdk::FunctionError;
+        CreateProposalError::StartTooSoon(10).panic();
+    }
+
     #[test]
     fn create_proposal() {
         let (_, mut ctr) = setup(&admin());
@@ -969,6 +1587,115 @@ mod unit_tests {
         assert_eq!(proposals[2].id, 3);
     }
 
+    #[test]
+    fn vote_change() {
+        fn vote(
+            ctr: &mut Contract,
+            id: u32,
+            token: u64,
+            voter: AccountId,
+            v: Vote,
+        ) -> Result<(), VoteError> {
+            ctr.on_vote_verified(
+                mk_human_sbt(token),
+                Some(AccountFlag::Verified),
+                id,
+                voter,
+                v,
+            )
+        }
+
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        let prop_id2 = mk_proposal(&mut ctr);
+        assert!(!ctr.vote_change_allowed(prop_id));
+        ctr.admin_allow_vote_change(prop_id, true);
+        assert!(ctr.vote_change_allowed(prop_id));
+        assert!(!ctr.vote_change_allowed(prop_id2));
+
+        bond_amount_call(&mut ctx, &mut ctr, alice(), 1);
+        bond_amount_call(&mut ctx, &mut ctr, bob(), 2);
+        ctx.block_timestamp = (START + 2) * MSECOND;
+        testing_env!(ctx.clone());
+        assert!(vote(
+            &mut ctr,
+            prop_id,
+            1,
+            alice(),
+            vec![candidate(1), candidate(2)]
+        )
+        .is_ok());
+        assert!(vote(&mut ctr, prop_id, 2, bob(), vec![candidate(1)]).is_ok());
+        // change vote
+        assert!(vote(&mut ctr, prop_id, 1, alice(), vec![candidate(3)]).is_ok());
+        let p = ctr._proposal(prop_id);
+        assert_eq!(p.voters_num, 2, "voters num should not change");
+        assert_eq!(p.result, vec![1, 0, 1]);
+        assert_eq!(p.voters.get(&1), Some(vec![2]));
+
+        // change to a blank ballot and back
+        assert!(vote(&mut ctr, prop_id, 1, alice(), vec![]).is_ok());
+        assert_eq!(ctr.blank_ballots(prop_id), 1);
+        assert!(vote(&mut ctr, prop_id, 1, alice(), vec![candidate(2)]).is_ok());
+        assert_eq!(ctr.blank_ballots(prop_id), 0);
+        let p = ctr._proposal(prop_id);
+        assert_eq!(p.voters_num, 2);
+        assert_eq!(p.result, vec![1, 1, 0]);
+
+        // vote change is not allowed by default
+        assert!(vote(&mut ctr, prop_id2, 1, alice(), vec![candidate(1)]).is_ok());
+        match vote(&mut ctr, prop_id2, 1, alice(), vec![candidate(2)]) {
+            Err(VoteError::DoubleVote(1)) => (),
+            x => panic!("expected DoubleVote(1), got: {:?}", x),
+        };
+        let p = ctr._proposal(prop_id2);
+        assert_eq!(p.voters_num, 1);
+        assert_eq!(p.result, vec![1, 0, 0]);
+    }
+
+    #[test]
+    fn total_votes_cast() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        let prop_id2 = mk_proposal(&mut ctr);
+        assert_eq!(ctr.total_votes_cast(), 0);
+
+        bond_amount_call(&mut ctx, &mut ctr, alice(), 1);
+        bond_amount_call(&mut ctx, &mut ctr, bob(), 2);
+        ctx.block_timestamp = (START + 2) * MSECOND;
+        testing_env!(ctx.clone());
+        for (token, voter) in [(1, alice()), (2, bob())] {
+            let res = ctr.on_vote_verified(
+                mk_human_sbt(token),
+                Some(AccountFlag::Verified),
+                prop_id,
+                voter,
+                vec![candidate(1)],
+            );
+            assert!(res.is_ok(), "expected OK, got: {:?}", res);
+        }
+        let res = ctr.on_vote_verified(
+            mk_human_sbt(1),
+            Some(AccountFlag::Verified),
+            prop_id2,
+            alice(),
+            vec![candidate(2)],
+        );
+        assert!(res.is_ok(), "expected OK, got: {:?}", res);
+
+        assert_eq!(ctr.total_votes_cast(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't update vote change of a started proposal")]
+    fn admin_allow_vote_change_started() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        ctx.block_timestamp = (START + 1) * MSECOND;
+        testing_env!(ctx);
+        ctr.admin_allow_vote_change(prop_id, true);
+    }
+
     #[test]
     fn vote_on_verified() {
         let (mut ctx, mut ctr) = setup(&admin());
@@ -1187,11 +1914,11 @@ mod unit_tests {
     #[test]
     #[should_panic(expected = "can only vote between proposal start and end time")]
     fn vote_wrong_time() {
-        let (_, mut ctr) = setup(&admin());
+        let (mut ctx, mut ctr) = setup(&admin());
 
         let prop_id = mk_proposal(&mut ctr);
         let vote: Vote = vec![candidate(1)];
-        ctr.vote(prop_id, vote);
+        vote_call(&mut ctx, &mut ctr, prop_id, vote);
     }
 
     #[test]
@@ -1202,9 +1929,8 @@ mod unit_tests {
         let prop_id = mk_proposal(&mut ctr);
         ctx.block_timestamp = (START + 2) * MSECOND;
         ctx.prepaid_gas = Gas(10 * Gas::ONE_TERA.0);
-        testing_env!(ctx);
 
-        ctr.vote(prop_id, vec![candidate(1)]);
+        vote_call(&mut ctx, &mut ctr, prop_id, vec![candidate(1)]);
     }
 
     #[test]
@@ -1217,9 +1943,8 @@ mod unit_tests {
         let prop_id = mk_proposal(&mut ctr);
         ctx.block_timestamp = (START + 2) * MSECOND;
         ctx.prepaid_gas = VOTE_GAS;
-        testing_env!(ctx);
 
-        ctr.vote(prop_id, vec![candidate(1)]);
+        vote_call(&mut ctx, &mut ctr, prop_id, vec![candidate(1)]);
     }
 
     #[test]
@@ -1237,24 +1962,97 @@ mod unit_tests {
         ctx.attached_deposit = 0;
         ctx.block_timestamp = (START + 2) * MSECOND;
         ctx.prepaid_gas = VOTE_GAS;
-        testing_env!(ctx);
 
-        ctr.vote(prop_id, vec![candidate(1)]);
+        vote_call(&mut ctx, &mut ctr, prop_id, vec![candidate(1)]);
     }
 
     #[test]
-    fn proposal_status_query() {
+    #[should_panic(
+        expected = "user didn't accept the voting policy, or the accepted voting policy doesn't match the required one"
+    )]
+    fn vote_stale_accepted_policy() {
         let (mut ctx, mut ctr) = setup(&admin());
-
         let prop_id = mk_proposal(&mut ctr);
-        let mut res = ctr.proposal_status(prop_id);
-        assert_eq!(res, Some(ProposalStatus::NOT_STARTED));
+        alice_voting_context(&mut ctx, &mut ctr);
 
-        ctx.block_timestamp = (START + 2) * MSECOND;
+        // same policy hash, but a new policy epoch
+        ctx.predecessor_account_id = admin();
         testing_env!(ctx.clone());
+        ctr.admin_set_policy(policy1());
 
-        res = ctr.proposal_status(prop_id);
-        assert_eq!(res, Some(ProposalStatus::ONGOING));
+        ctx.predecessor_account_id = alice();
+        vote_call(&mut ctx, &mut ctr, prop_id, vec![candidate(1)]);
+    }
+
+    #[test]
+    fn accept_policy_after_policy_change() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        alice_voting_context(&mut ctx, &mut ctr);
+        assert!(ctr.has_accepted_policy(&alice()));
+        assert_eq!(ctr.policy_epoch(), 0);
+
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx.clone());
+        ctr.admin_set_policy(policy1());
+        assert_eq!(ctr.policy_epoch(), 1);
+        assert!(!ctr.has_accepted_policy(&alice()));
+        // stale acceptances are not counted
+        assert_eq!(ctr.policy_count, 0);
+        assert_eq!(ctr.eligible_voter_count(), 0);
+        assert_eq!(ctr.accept_count_for_policy(policy1(), None), 0);
+        // counters of the previous epoch are still queryable
+        assert_eq!(ctr.accept_count_for_policy(policy1(), Some(0)), 1);
+
+        // re-accepting the same policy renews the acceptance in the new epoch
+        ctx.predecessor_account_id = alice();
+        ctx.attached_deposit = ACCEPT_POLICY_COST;
+        testing_env!(ctx.clone());
+        ctr.accept_fair_voting_policy(policy1());
+        assert!(ctr.has_accepted_policy(&alice()));
+        assert_eq!(ctr.policy_count, 1);
+        assert_eq!(ctr.accept_count_for_policy(policy1(), None), 1);
+
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx.clone());
+        ctr.admin_set_policy(policy2());
+        assert_eq!(ctr.policy(), policy2());
+        assert!(!ctr.has_accepted_policy(&alice()));
+
+        ctx.predecessor_account_id = alice();
+        testing_env!(ctx.clone());
+        ctr.accept_fair_voting_policy(policy2());
+        assert!(ctr.has_accepted_policy(&alice()));
+        assert_eq!(ctr.accept_count_for_policy(policy1(), None), 0);
+        assert_eq!(ctr.accept_count_for_policy(policy2(), None), 1);
+        // alice is still counted in the retired epoch
+        assert_eq!(ctr.accept_count_for_policy(policy1(), Some(1)), 1);
+        assert_eq!(ctr.accept_count_for_policy(policy2(), Some(1)), 0);
+
+        ctx.attached_deposit = 0;
+        vote_call(&mut ctx, &mut ctr, prop_id, vec![candidate(1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not an admin")]
+    fn admin_set_policy_not_admin() {
+        let (_, mut ctr) = setup(&alice());
+        ctr.admin_set_policy(policy2());
+    }
+
+    #[test]
+    fn proposal_status_query() {
+        let (mut ctx, mut ctr) = setup(&admin());
+
+        let prop_id = mk_proposal(&mut ctr);
+        let mut res = ctr.proposal_status(prop_id);
+        assert_eq!(res, Some(ProposalStatus::NOT_STARTED));
+
+        ctx.block_timestamp = (START + 2) * MSECOND;
+        testing_env!(ctx.clone());
+
+        res = ctr.proposal_status(prop_id);
+        assert_eq!(res, Some(ProposalStatus::ONGOING));
 
         ctx.block_timestamp = (START + 11) * MSECOND;
         testing_env!(ctx.clone());
@@ -1280,6 +2078,304 @@ mod unit_tests {
         assert_eq!(res, Some(ProposalStatus::ENDED));
     }
 
+    #[test]
+    fn is_human_issuer() {
+        assert_eq!(Contract::is_human_issuer(&mk_human_sbt(1)), (true, 1));
+        assert_eq!(
+            Contract::is_human_issuer(&mk_human_sbts(vec![3, 2, 5])),
+            (true, 3)
+        );
+        assert_eq!(Contract::is_human_issuer(&mk_nohuman_sbt(1)), (false, 0));
+        assert_eq!(
+            Contract::is_human_issuer(&mk_human_sbts(vec![])),
+            (false, 0)
+        );
+        assert_eq!(Contract::is_human_issuer(&vec![]), (false, 0));
+    }
+
+    #[test]
+    fn vote_multiple_tokens_one_issuer() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        ctx.block_timestamp = (START + 2) * MSECOND;
+        ctx.attached_deposit = BOND_AMOUNT;
+        ctx.predecessor_account_id = sbt_registry();
+        testing_env!(ctx);
+
+        // the first token is used for bonding and voting
+        ctr.bond(
+            alice(),
+            mk_human_sbts(vec![5, 6]),
+            Value::String("".to_string()),
+        );
+        assert_eq!(ctr.bonded_amounts.get(&5), Some(BOND_AMOUNT));
+        assert_eq!(ctr.bonded_amounts.get(&6), None);
+
+        match ctr.on_vote_verified(
+            mk_human_sbts(vec![5, 6]),
+            Some(AccountFlag::Verified),
+            prop_id,
+            alice(),
+            vec![candidate(1)],
+        ) {
+            Ok(_) => (),
+            x => panic!("expected OK, got: {:?}", x),
+        };
+        let p = ctr._proposal(prop_id);
+        assert_eq!(p.voters_num, 1);
+        assert_eq!(p.voters.get(&5), Some(vec![0]));
+        assert_eq!(p.voters.get(&6), None);
+        assert_eq!(p.user_sbt.get(&alice()), Some(5));
+    }
+
+    #[test]
+    fn pending_proposals_for() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop1 = mk_proposal(&mut ctr);
+        let prop2 = mk_proposal(&mut ctr);
+        let prop3 = mk_proposal(&mut ctr);
+        let prop4 = mk_proposal(&mut ctr);
+        ctr.admin_cancel_proposal(prop4);
+        // proposals didn't start yet
+        assert_eq!(ctr.pending_proposals_for(ALICE_SBT), vec![]);
+
+        alice_voting_context(&mut ctx, &mut ctr);
+        assert_eq!(
+            ctr.pending_proposals_for(ALICE_SBT),
+            vec![prop1, prop2, prop3]
+        );
+
+        let iah_proof = mk_human_sbt(ALICE_SBT);
+        let flag = Some(AccountFlag::Verified);
+        for prop_id in [prop1, prop3] {
+            match ctr.on_vote_verified(
+                iah_proof.clone(),
+                flag.clone(),
+                prop_id,
+                alice(),
+                vec![candidate(1)],
+            ) {
+                Ok(_) => (),
+                x => panic!("expected OK, got: {:?}", x),
+            };
+        }
+        assert_eq!(ctr.pending_proposals_for(ALICE_SBT), vec![prop2]);
+        assert_eq!(
+            ctr.pending_proposals_for(ALICE_SBT + 1),
+            vec![prop1, prop2, prop3]
+        );
+
+        // no pending proposals after the voting ends
+        ctx.block_timestamp = (START + 11) * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(ctr.pending_proposals_for(ALICE_SBT), vec![]);
+    }
+
+    #[test]
+    fn proposal_view_status() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        let prop_id2 = mk_proposal(&mut ctr);
+        ctr.admin_cancel_proposal(prop_id2);
+
+        let timeline = [
+            (START, ProposalStatus::NOT_STARTED),
+            (START + 1, ProposalStatus::ONGOING),
+            (START + 10, ProposalStatus::ONGOING),
+            (START + 11, ProposalStatus::COOLDOWN),
+            (START + 110, ProposalStatus::COOLDOWN),
+            (START + 111, ProposalStatus::ENDED),
+        ];
+        for (t, status) in timeline {
+            ctx.block_timestamp = t * MSECOND;
+            testing_env!(ctx.clone());
+            assert_eq!(ctr.proposal(prop_id).status, status);
+            assert_eq!(ctr.proposal_status(prop_id), Some(status));
+            let proposals = ctr.proposals();
+            assert_eq!(proposals[1].status, ProposalStatus::CANCELLED);
+            assert_eq!(ctr.proposal(prop_id2).status, ProposalStatus::CANCELLED);
+        }
+    }
+
+    #[test]
+    fn ref_link() {
+        let (_, mut ctr) = setup(&admin());
+        let prop_id = ctr
+            .create_proposal(
+                crate::ProposalType::HouseOfMerit,
+                START + 1,
+                START + 10,
+                100,
+                String::from("near.social/post/123"),
+                2,
+                2,
+                vec![candidate(1), candidate(2)],
+                2,
+            )
+            .unwrap();
+        assert_eq!(
+            ctr.ref_link(prop_id),
+            Some("near.social/post/123".to_string())
+        );
+        assert_eq!(ctr.proposal(prop_id).ref_link, "near.social/post/123");
+        assert_eq!(ctr.ref_link(prop_id + 1), None);
+    }
+
+    #[test]
+    fn proposal_params() {
+        let (_, mut ctr) = setup(&admin());
+        let prop_id = ctr
+            .create_proposal(
+                crate::ProposalType::HouseOfMerit,
+                START + 1,
+                START + 10,
+                100,
+                String::from("ref_link.io"),
+                5,
+                2,
+                vec![candidate(1), candidate(2), candidate(3)],
+                3,
+            )
+            .unwrap();
+        assert_eq!(ctr.proposal_params(prop_id), Some((2, 3, 5)));
+        assert_eq!(ctr.proposal_params(prop_id + 1), None);
+    }
+
+    #[test]
+    fn is_election_over() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        mk_proposal(&mut ctr);
+        // finish_time = end + cooldown
+        assert_eq!(ctr.finish_time(), START + 110);
+        assert!(!ctr.is_election_over());
+
+        ctx.block_timestamp = (START + 110) * MSECOND;
+        testing_env!(ctx.clone());
+        assert!(!ctr.is_election_over());
+
+        ctx.block_timestamp = (START + 111) * MSECOND;
+        testing_env!(ctx);
+        assert!(ctr.is_election_over());
+    }
+
+    #[test]
+    fn vote_gas_requirement() {
+        let (_, ctr) = setup(&admin());
+        assert_eq!(ctr.vote_gas_requirement().0, VOTE_GAS.0);
+    }
+
+    #[test]
+    fn will_mint_i_voted() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop1 = mk_proposal(&mut ctr);
+        let prop2 = mk_proposal(&mut ctr);
+        let prop3 = mk_proposal(&mut ctr);
+        ctr.admin_cancel_proposal(prop3);
+        assert!(!ctr.will_mint_i_voted(ALICE_SBT));
+
+        alice_voting_context(&mut ctx, &mut ctr);
+        let iah_proof = mk_human_sbt(ALICE_SBT);
+        let flag = Some(AccountFlag::Verified);
+        match ctr.on_vote_verified(
+            iah_proof.clone(),
+            flag.clone(),
+            prop1,
+            alice(),
+            vec![candidate(1)],
+        ) {
+            Ok(_) => (),
+            x => panic!("expected OK, got: {:?}", x),
+        };
+        // voted only on some proposals
+        assert!(!ctr.will_mint_i_voted(ALICE_SBT));
+
+        match ctr.on_vote_verified(iah_proof, flag, prop2, alice(), vec![candidate(1)]) {
+            Ok(_) => (),
+            x => panic!("expected OK, got: {:?}", x),
+        };
+        // voted on all proposals, the cancelled one is skipped
+        assert!(ctr.will_mint_i_voted(ALICE_SBT));
+        assert!(!ctr.will_mint_i_voted(ALICE_SBT + 1));
+    }
+
+    #[test]
+    fn admin_cancel_proposal() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        let prop_id2 = mk_proposal(&mut ctr);
+
+        ctx.block_timestamp = (START + 2) * MSECOND;
+        testing_env!(ctx);
+        ctr.admin_cancel_proposal(prop_id);
+        assert_eq!(
+            ctr.proposal_status(prop_id),
+            Some(ProposalStatus::CANCELLED)
+        );
+        assert_eq!(ctr.proposal_status(prop_id2), Some(ProposalStatus::ONGOING));
+    }
+
+    #[test]
+    fn admin_set_quorum() {
+        let (_, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        assert_eq!(ctr.proposal(prop_id).quorum, 2);
+        ctr.admin_set_quorum(prop_id, 10);
+        // `winners_by_proposal` reads the quorum from the stored proposal
+        assert_eq!(ctr.proposal(prop_id).quorum, 10);
+        assert_eq!(ctr._proposal(prop_id).quorum, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't update quorum of a started proposal")]
+    fn admin_set_quorum_started() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        ctx.block_timestamp = (START + 1) * MSECOND;
+        testing_env!(ctx);
+        ctr.admin_set_quorum(prop_id, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "not an admin")]
+    fn admin_set_quorum_not_admin() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        ctx.predecessor_account_id = alice();
+        testing_env!(ctx);
+        ctr.admin_set_quorum(prop_id, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "proposal is cancelled")]
+    fn vote_cancelled_proposal() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        ctr.admin_cancel_proposal(prop_id);
+
+        alice_voting_context(&mut ctx, &mut ctr);
+        vote_call(&mut ctx, &mut ctr, prop_id, vec![candidate(1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not an admin")]
+    fn admin_cancel_proposal_not_admin() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        ctx.predecessor_account_id = alice();
+        testing_env!(ctx);
+        ctr.admin_cancel_proposal(prop_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't cancel an ended proposal")]
+    fn admin_cancel_proposal_ended() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        ctx.block_timestamp = (START + 111) * MSECOND;
+        testing_env!(ctx);
+        ctr.admin_cancel_proposal(prop_id);
+    }
+
     #[test]
     #[should_panic(expected = "double vote for the same option")]
     fn vote_double_vote_same_candidate() {
@@ -1287,7 +2383,12 @@ mod unit_tests {
 
         let prop_id = mk_proposal(&mut ctr);
         alice_voting_context(&mut ctx, &mut ctr);
-        ctr.vote(prop_id, vec![candidate(1), candidate(1)]);
+        vote_call(
+            &mut ctx,
+            &mut ctr,
+            prop_id,
+            vec![candidate(1), candidate(1)],
+        );
     }
 
     #[test]
@@ -1297,7 +2398,7 @@ mod unit_tests {
 
         let prop_id = mk_proposal(&mut ctr);
         alice_voting_context(&mut ctx, &mut ctr);
-        ctr.vote(prop_id, vec![bob()]);
+        vote_call(&mut ctx, &mut ctr, prop_id, vec![bob()]);
     }
 
     #[test]
@@ -1307,7 +2408,12 @@ mod unit_tests {
 
         let prop_id = mk_proposal(&mut ctr);
         alice_voting_context(&mut ctx, &mut ctr);
-        ctr.vote(prop_id, vec![candidate(1), candidate(2), candidate(3)]);
+        vote_call(
+            &mut ctx,
+            &mut ctr,
+            prop_id,
+            vec![candidate(1), candidate(2), candidate(3)],
+        );
     }
 
     #[test]
@@ -1317,7 +2423,7 @@ mod unit_tests {
         let prop_id = mk_proposal(&mut ctr);
         alice_voting_context(&mut ctx, &mut ctr);
         // should not panic
-        ctr.vote(prop_id, vec![]);
+        vote_call(&mut ctx, &mut ctr, prop_id, vec![]);
         // note: we can only check vote result and state change through an integration test.
     }
 
@@ -1328,7 +2434,7 @@ mod unit_tests {
 
         let prop_id = mk_proposal_setup_package(&mut ctr);
         alice_voting_context(&mut ctx, &mut ctr);
-        ctr.vote(prop_id, vec![]);
+        vote_call(&mut ctx, &mut ctr, prop_id, vec![]);
     }
 
     #[test]
@@ -1338,7 +2444,7 @@ mod unit_tests {
 
         let prop_id = mk_proposal_setup_package(&mut ctr);
         alice_voting_context(&mut ctx, &mut ctr);
-        ctr.vote(prop_id, vec![candidate(1)]);
+        vote_call(&mut ctx, &mut ctr, prop_id, vec![candidate(1)]);
     }
 
     #[test]
@@ -1350,11 +2456,111 @@ mod unit_tests {
         let prop_hom2 = mk_proposal(&mut ctr);
         alice_voting_context(&mut ctx, &mut ctr);
 
-        ctr.vote(prop_sp, setup_package_candidates()[0..=0].to_vec());
-        ctr.vote(prop_hom1, vec![]);
+        vote_call(
+            &mut ctx,
+            &mut ctr,
+            prop_sp,
+            setup_package_candidates()[0..=0].to_vec(),
+        );
+        vote_call(&mut ctx, &mut ctr, prop_hom1, vec![]);
         // need to setup new context, otherwise we have a gas error
         alice_voting_context(&mut ctx, &mut ctr);
-        ctr.vote(prop_hom2, vec![candidate(2), candidate(1)]);
+        vote_call(
+            &mut ctx,
+            &mut ctr,
+            prop_hom2,
+            vec![candidate(2), candidate(1)],
+        );
+    }
+
+    #[test]
+    fn vote_locked() -> Result<(), VoteError> {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        alice_voting_context(&mut ctx, &mut ctr);
+        ctx.predecessor_account_id = sbt_registry();
+        testing_env!(ctx);
+
+        let vote = vec![candidate(1)];
+        ctr.vote(
+            alice(),
+            START + 11,
+            None,
+            VotePayload {
+                prop_id,
+                vote: vote.clone(),
+            },
+        );
+        // the vote is recorded in the callback
+        assert_eq!(ctr.vote_by_token(prop_id, ALICE_SBT), None);
+        ctr.on_vote_verified(
+            mk_human_sbt(ALICE_SBT),
+            Some(AccountFlag::Verified),
+            prop_id,
+            alice(),
+            vote.clone(),
+        )?;
+        assert_eq!(ctr.vote_by_token(prop_id, ALICE_SBT), Some(vote));
+        assert_eq!(ctr._proposal(prop_id).voters_num, 1);
+        assert_eq!(ctr.total_votes_cast(), 1);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "IAH SBT must be locked until the proposal end")]
+    fn vote_insufficient_lock() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        alice_voting_context(&mut ctx, &mut ctr);
+        ctx.predecessor_account_id = sbt_registry();
+        testing_env!(ctx);
+
+        ctr.vote(
+            alice(),
+            START + 10,
+            None,
+            VotePayload {
+                prop_id,
+                vote: vec![candidate(1)],
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must be called by the registry")]
+    fn vote_not_registry() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        alice_voting_context(&mut ctx, &mut ctr);
+
+        ctr.vote(
+            alice(),
+            START + 11,
+            None,
+            VotePayload {
+                prop_id,
+                vote: vec![candidate(1)],
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "IAH SBT must be locked until the proposal end")]
+    fn vote_many_insufficient_lock() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop1 = mk_proposal(&mut ctr);
+        let prop2 = mk_proposal(&mut ctr);
+        alice_voting_context(&mut ctx, &mut ctr);
+        ctx.predecessor_account_id = sbt_registry();
+        ctx.prepaid_gas = Gas(VOTE_GAS.0 + VOTE_GAS_CALLBACK.0);
+        testing_env!(ctx);
+
+        ctr.vote_many(
+            alice(),
+            START + 10,
+            None,
+            vec![(prop1, vec![candidate(1)]), (prop2, vec![candidate(2)])],
+        );
     }
 
     #[test]
@@ -1462,6 +2668,127 @@ mod unit_tests {
         assert!(p2.user_sbt.get(&alice()).is_none());
     }
 
+    #[test]
+    fn vote_many() -> Result<(), VoteError> {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop1 = mk_proposal(&mut ctr);
+        let prop2 = mk_proposal(&mut ctr);
+        alice_voting_context(&mut ctx, &mut ctr);
+        ctx.prepaid_gas = Gas(VOTE_GAS.0 + VOTE_GAS_CALLBACK.0);
+
+        let votes = vec![
+            (prop1, vec![candidate(1)]),
+            (prop2, vec![candidate(2), candidate(3)]),
+        ];
+        vote_many_call(&mut ctx, &mut ctr, votes.clone());
+
+        // callback
+        ctr.on_vote_many_verified(
+            mk_human_sbt(ALICE_SBT),
+            Some(AccountFlag::Verified),
+            alice(),
+            votes,
+        )?;
+        assert_eq!(
+            ctr.vote_by_token(prop1, ALICE_SBT),
+            Some(vec![candidate(1)])
+        );
+        assert_eq!(
+            ctr.vote_by_token(prop2, ALICE_SBT),
+            Some(vec![candidate(2), candidate(3)])
+        );
+        assert_eq!(ctr.total_votes_cast(), 2);
+
+        // per proposal validation is preserved
+        match ctr.on_vote_many_verified(
+            mk_human_sbt(ALICE_SBT),
+            Some(AccountFlag::Verified),
+            alice(),
+            vec![(prop1, vec![candidate(2)])],
+        ) {
+            Err(VoteError::DoubleVote(ALICE_SBT)) => (),
+            x => panic!("expected DoubleVote, got: {:?}", x),
+        };
+        match ctr.on_vote_many_verified(
+            mk_human_sbt(ALICE_SBT + 1),
+            Some(AccountFlag::Verified),
+            bob(),
+            vec![(prop1, vec![candidate(2)])],
+        ) {
+            Err(VoteError::NoBond) => (),
+            x => panic!("expected NoBond, got: {:?}", x),
+        };
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "not enough gas")]
+    fn vote_many_not_enough_gas() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop1 = mk_proposal(&mut ctr);
+        let prop2 = mk_proposal(&mut ctr);
+        alice_voting_context(&mut ctx, &mut ctr);
+        vote_many_call(
+            &mut ctx,
+            &mut ctr,
+            vec![(prop1, vec![candidate(1)]), (prop2, vec![candidate(2)])],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "proposal is cancelled")]
+    fn vote_many_cancelled_proposal() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop1 = mk_proposal(&mut ctr);
+        let prop2 = mk_proposal(&mut ctr);
+        ctr.admin_cancel_proposal(prop2);
+        alice_voting_context(&mut ctx, &mut ctr);
+        ctx.prepaid_gas = Gas(VOTE_GAS.0 + VOTE_GAS_CALLBACK.0);
+        vote_many_call(
+            &mut ctx,
+            &mut ctr,
+            vec![(prop1, vec![candidate(1)]), (prop2, vec![candidate(2)])],
+        );
+    }
+
+    #[test]
+    fn vote_by_token() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        bond_amount_call(&mut ctx, &mut ctr, alice(), 1);
+        bond_amount_call(&mut ctx, &mut ctr, bob(), 2);
+        ctx.block_timestamp = (START + 2) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.vote_by_token(prop_id, 1), None);
+
+        for (token, voter, vote) in [
+            (1, alice(), vec![candidate(3), candidate(1)]),
+            (2, bob(), vec![]),
+        ] {
+            let res = ctr.on_vote_verified(
+                mk_human_sbt(token),
+                Some(AccountFlag::Verified),
+                prop_id,
+                voter,
+                vote,
+            );
+            assert!(res.is_ok(), "expected OK, got: {:?}", res);
+        }
+        assert_eq!(
+            ctr.vote_by_token(prop_id, 1),
+            Some(vec![candidate(3), candidate(1)])
+        );
+        assert_eq!(ctr.vote_by_token(prop_id, 2), Some(vec![]));
+        assert_eq!(ctr.vote_by_token(prop_id, 3), None);
+        assert_eq!(ctr.vote_by_token(prop_id + 1, 1), None);
+
+        // revoked vote
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx);
+        assert!(ctr.admin_revoke_vote(prop_id, vec![1]).is_ok());
+        assert_eq!(ctr.vote_by_token(prop_id, 1), None);
+    }
+
     #[test]
     fn admin_revoke_vote() {
         let (mut ctx, mut ctr) = setup(&admin());
@@ -1508,6 +2835,41 @@ mod unit_tests {
         assert_eq!(test_utils::get_logs()[1], expected_event);
     }
 
+    #[test]
+    fn admin_revoke_vote_blank_ballot() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        ctx.block_timestamp = (START + 2) * MSECOND;
+        for (token_id, voter, vote) in [(1, alice(), vec![]), (2, bob(), vec![candidate(1)])] {
+            bond_amount_call(&mut ctx, &mut ctr, voter.clone(), token_id);
+            let res = ctr.on_vote_verified(
+                mk_human_sbt(token_id),
+                Some(AccountFlag::Verified),
+                prop_id,
+                voter,
+                vote,
+            );
+            assert!(res.is_ok(), "expected OK, got: {:?}", res);
+        }
+        assert_eq!(ctr.blank_ballots(prop_id), 1);
+
+        // revoking a non blank vote doesn't change the blank ballots counter
+        match ctr.admin_revoke_vote(prop_id, vec![2]) {
+            Ok(_) => (),
+            x => panic!("expected OK, got: {:?}", x),
+        }
+        assert_eq!(ctr.blank_ballots(prop_id), 1);
+
+        match ctr.admin_revoke_vote(prop_id, vec![1]) {
+            Ok(_) => (),
+            x => panic!("expected OK, got: {:?}", x),
+        }
+        assert_eq!(ctr.blank_ballots(prop_id), 0);
+        let p = ctr._proposal(prop_id);
+        assert_eq!(p.voters_num, 0);
+        assert_eq!(p.result, vec![0, 0, 0]);
+    }
+
     #[test]
     fn has_voted_on_all_proposals() {
         let (mut ctx, mut ctr) = setup(&admin());
@@ -1563,19 +2925,40 @@ mod unit_tests {
         };
         assert!(!ctr.has_voted_on_all_proposals(alice()));
 
-        // fourth vote (voting completed)
-        prop_id = prop4;
-        match ctr.on_vote_verified(
-            mk_human_sbt(1),
+        // fourth vote (voting completed)
+        prop_id = prop4;
+        match ctr.on_vote_verified(
+            mk_human_sbt(1),
+            Some(AccountFlag::Verified),
+            prop_id,
+            alice(),
+            vec![candidate(3), candidate(2)],
+        ) {
+            Ok(_) => (),
+            x => panic!("expected OK, got: {:?}", x),
+        };
+        assert!(ctr.has_voted_on_all_proposals(alice()));
+    }
+
+    #[test]
+    fn has_voted_on_all_proposals_cancelled() -> Result<(), VoteError> {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop1 = mk_proposal(&mut ctr);
+        let prop2 = mk_proposal(&mut ctr);
+        ctr.admin_cancel_proposal(prop2);
+        alice_voting_context(&mut ctx, &mut ctr);
+        assert!(!ctr.has_voted_on_all_proposals(alice()));
+
+        ctr.on_vote_verified(
+            mk_human_sbt(ALICE_SBT),
             Some(AccountFlag::Verified),
-            prop_id,
+            prop1,
             alice(),
-            vec![candidate(3), candidate(2)],
-        ) {
-            Ok(_) => (),
-            x => panic!("expected OK, got: {:?}", x),
-        };
+            vec![candidate(1)],
+        )?;
+        // cancelled proposal can't be voted on, so it is skipped
         assert!(ctr.has_voted_on_all_proposals(alice()));
+        Ok(())
     }
 
     #[test]
@@ -1590,6 +2973,55 @@ mod unit_tests {
         assert_eq!(ctr.bonded_amounts.get(&2), Some(BOND_AMOUNT));
     }
 
+    #[test]
+    fn slash_bond_partial() {
+        let (mut ctx, mut ctr) = setup(&alice());
+        const SLASH: Balance = BOND_AMOUNT / 3;
+        bond_amount_call(&mut ctx, &mut ctr, alice(), ALICE_SBT);
+        assert_eq!(ctr.bond_of(ALICE_SBT).0, BOND_AMOUNT);
+
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx);
+        ctr.slash_bond_partial(ALICE_SBT, U128(SLASH));
+        assert_eq!(ctr.bond_of(ALICE_SBT).0, BOND_AMOUNT - SLASH);
+        assert_eq!(ctr.total_slashed, SLASH);
+
+        // slash the remaining bond
+        ctr.slash_bond_partial(ALICE_SBT, U128(BOND_AMOUNT - SLASH));
+        assert_eq!(ctr.bond_of(ALICE_SBT).0, 0);
+        assert_eq!(ctr.total_slashed, BOND_AMOUNT);
+        assert_eq!(ctr.bond_of(ALICE_SBT + 1).0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "amount must be positive and not bigger than the bond")]
+    fn slash_bond_partial_too_much() {
+        let (mut ctx, mut ctr) = setup(&alice());
+        bond_amount_call(&mut ctx, &mut ctr, alice(), ALICE_SBT);
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx);
+        ctr.slash_bond_partial(ALICE_SBT, U128(BOND_AMOUNT + 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "can only slash before the elections finish time")]
+    fn slash_bond_partial_after_finish() {
+        let (mut ctx, mut ctr) = setup(&alice());
+        bond_amount_call(&mut ctx, &mut ctr, alice(), ALICE_SBT);
+        ctx.predecessor_account_id = admin();
+        ctx.block_timestamp = (ctr.finish_time + 1) * MSECOND;
+        testing_env!(ctx);
+        ctr.slash_bond_partial(ALICE_SBT, U128(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "not an admin")]
+    fn slash_bond_partial_not_admin() {
+        let (mut ctx, mut ctr) = setup(&alice());
+        bond_amount_call(&mut ctx, &mut ctr, alice(), ALICE_SBT);
+        ctr.slash_bond_partial(ALICE_SBT, U128(1));
+    }
+
     #[test]
     #[should_panic(expected = "Err(NoBond)")]
     fn vote_without_bond_amount() {
@@ -1623,8 +3055,8 @@ mod unit_tests {
         assert_eq!(ctr.bonded_amounts.get(&1), Some(BOND_AMOUNT));
 
         // cast a vote and call on_vote_verified callbacks.
-        ctr.vote(prop_sp, vote_sp.clone());
-        ctr.vote(prop1, vote1.clone());
+        vote_call(&mut ctx, &mut ctr, prop_sp, vote_sp.clone());
+        vote_call(&mut ctx, &mut ctr, prop1, vote1.clone());
         let iah_proof = vec![(alice(), vec![ALICE_SBT])];
         let flag = Some(AccountFlag::Verified);
         ctr.on_vote_verified(iah_proof.clone(), flag.clone(), prop1, alice(), vote1)?;
@@ -1647,6 +3079,268 @@ mod unit_tests {
         Ok(())
     }
 
+    #[test]
+    fn eligible_voter_count() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        assert_eq!(ctr.eligible_voter_count(), 0);
+
+        // alice accepts the policy and bonds
+        alice_voting_context(&mut ctx, &mut ctr);
+        assert_eq!(ctr.eligible_voter_count(), 1);
+
+        // bonding again or re-accepting the policy doesn't change the counters
+        bond_amount_call(&mut ctx, &mut ctr, alice(), ALICE_SBT);
+        ctx.attached_deposit = ACCEPT_POLICY_COST;
+        testing_env!(ctx.clone());
+        ctr.accept_fair_voting_policy(policy1());
+        assert_eq!((ctr.bonded_count, ctr.policy_count), (1, 1));
+
+        // bob only bonds, admin only accepts the policy
+        bond_amount_call(&mut ctx, &mut ctr, bob(), 2);
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx.clone());
+        ctr.accept_fair_voting_policy(policy1());
+        assert_eq!((ctr.bonded_count, ctr.policy_count), (2, 2));
+        assert_eq!(ctr.eligible_voter_count(), 2);
+
+        // charlie accepts the policy without bonding
+        ctx.predecessor_account_id = charlie();
+        testing_env!(ctx.clone());
+        ctr.accept_fair_voting_policy(policy1());
+        assert_eq!(ctr.eligible_voter_count(), 2);
+
+        // unbond
+        ctx.block_timestamp = (ctr.finish_time + 1) * MSECOND;
+        ctx.predecessor_account_id = sbt_registry();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx);
+        ctr.unbond(alice(), mk_human_sbt(1), Value::String("".to_string()));
+        assert_eq!((ctr.bonded_count, ctr.policy_count), (1, 2));
+        assert_eq!(ctr.eligible_voter_count(), 1);
+    }
+
+    #[test]
+    fn accept_count_for_policy() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        assert_eq!(ctr.accept_count_for_policy(policy1(), None), 0);
+
+        ctx.attached_deposit = ACCEPT_POLICY_COST;
+        for user in [alice(), bob(), charlie()] {
+            ctx.predecessor_account_id = user;
+            testing_env!(ctx.clone());
+            ctr.accept_fair_voting_policy(policy1());
+        }
+        // re-accepting the same policy doesn't change the counter
+        ctr.accept_fair_voting_policy(policy1());
+        assert_eq!(ctr.accept_count_for_policy(policy1(), None), 3);
+        assert_eq!(ctr.accept_count_for_policy(policy2(), None), 0);
+
+        // charlie and bob move to the new policy
+        ctr.accept_fair_voting_policy(policy2());
+        ctx.predecessor_account_id = bob();
+        testing_env!(ctx.clone());
+        ctr.accept_fair_voting_policy(policy2());
+        assert_eq!(ctr.accept_count_for_policy(policy1(), None), 1);
+        assert_eq!(ctr.accept_count_for_policy(policy2(), None), 2);
+
+        // unbond removes the policy acceptance
+        bond_amount_call(&mut ctx, &mut ctr, alice(), ALICE_SBT);
+        ctx.block_timestamp = (ctr.finish_time + 1) * MSECOND;
+        ctx.predecessor_account_id = sbt_registry();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx);
+        ctr.unbond(
+            alice(),
+            mk_human_sbt(ALICE_SBT),
+            Value::String("".to_string()),
+        );
+        assert_eq!(ctr.accept_count_for_policy(policy1(), None), 0);
+        assert_eq!(ctr.accept_count_for_policy(policy2(), None), 2);
+    }
+
+    #[test]
+    fn rebond() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let rebond_call = |ctx: &mut VMContext, ctr: &mut Contract| {
+            ctx.predecessor_account_id = sbt_registry();
+            ctx.attached_deposit = BOND_AMOUNT;
+            testing_env!(ctx.clone());
+            ctr.rebond(
+                alice(),
+                mk_human_sbt(ALICE_SBT),
+                Value::String("".to_string()),
+            )
+        };
+
+        // can't rebond when still bonded
+        alice_voting_context(&mut ctx, &mut ctr);
+        assert!(matches!(
+            rebond_call(&mut ctx, &mut ctr),
+            PromiseOrValue::Promise(_)
+        ));
+        assert_eq!(ctr.bonded_amounts.get(&ALICE_SBT), Some(BOND_AMOUNT));
+        assert_eq!(ctr.bonded_count, 1);
+
+        // unbond after the elections
+        ctx.block_timestamp = (ctr.finish_time + 1) * MSECOND;
+        ctx.predecessor_account_id = sbt_registry();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        ctr.unbond(
+            alice(),
+            mk_human_sbt(ALICE_SBT),
+            Value::String("".to_string()),
+        );
+        assert_eq!(ctr.bonded_amounts.get(&ALICE_SBT), None);
+        assert_eq!(ctr.bonded_count, 0);
+
+        // can't rebond after finish_time
+        assert!(matches!(
+            rebond_call(&mut ctx, &mut ctr),
+            PromiseOrValue::Promise(_)
+        ));
+        assert_eq!(ctr.bonded_amounts.get(&ALICE_SBT), None);
+
+        // elections are reopened
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx.clone());
+        ctr.admin_set_finish_time(ctr.finish_time + 100);
+
+        match rebond_call(&mut ctx, &mut ctr) {
+            PromiseOrValue::Value(v) => assert_eq!(v.0, BOND_AMOUNT),
+            _ => panic!("expected rebond to succeed"),
+        }
+        assert_eq!(ctr.bonded_amounts.get(&ALICE_SBT), Some(BOND_AMOUNT));
+        assert_eq!(ctr.bonded_count, 1);
+
+        // rebonding again doesn't double count
+        assert!(matches!(
+            rebond_call(&mut ctx, &mut ctr),
+            PromiseOrValue::Promise(_)
+        ));
+        assert_eq!(ctr.bonded_amounts.get(&ALICE_SBT), Some(BOND_AMOUNT));
+        assert_eq!(ctr.bonded_count, 1);
+    }
+
+    #[test]
+    fn expected_unbond() -> Result<(), VoteError> {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop1 = mk_proposal(&mut ctr);
+        let prop2 = mk_proposal(&mut ctr);
+        assert_eq!(ctr.expected_unbond(ALICE_SBT).0, 0);
+
+        alice_voting_context(&mut ctx, &mut ctr);
+        // didn't vote on all proposals: full bond is returned
+        assert_eq!(ctr.expected_unbond(ALICE_SBT).0, BOND_AMOUNT);
+
+        let iah_proof = vec![(alice(), vec![ALICE_SBT])];
+        let flag = Some(AccountFlag::Verified);
+        let vote = vec![candidate(1)];
+        ctr.on_vote_verified(
+            iah_proof.clone(),
+            flag.clone(),
+            prop1,
+            alice(),
+            vote.clone(),
+        )?;
+        assert_eq!(ctr.expected_unbond(ALICE_SBT).0, BOND_AMOUNT);
+
+        // voted on all proposals: I Voted SBT will be minted
+        ctr.on_vote_verified(iah_proof, flag, prop2, alice(), vote)?;
+        assert_eq!(ctr.expected_unbond(ALICE_SBT).0, BOND_AMOUNT - MINT_COST);
+
+        // bond doesn't cover the mint cost
+        ctr.bonded_amounts.insert(&ALICE_SBT, &(MINT_COST - 1));
+        assert_eq!(ctr.expected_unbond(ALICE_SBT).0, MINT_COST - 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unbond_bond_below_mint_cost() -> Result<(), VoteError> {
+        let (mut ctx, mut ctr) = setup(&admin());
+
+        let prop1 = mk_proposal(&mut ctr);
+        let vote1 = vec![candidate(3), candidate(1)];
+
+        alice_voting_context(&mut ctx, &mut ctr);
+        vote_call(&mut ctx, &mut ctr, prop1, vote1.clone());
+        let iah_proof = vec![(alice(), vec![ALICE_SBT])];
+        ctr.on_vote_verified(
+            iah_proof,
+            Some(AccountFlag::Verified),
+            prop1,
+            alice(),
+            vote1,
+        )?;
+        // simulate a bond which can't cover the mint cost
+        ctr.bonded_amounts.insert(&ALICE_SBT, &(MINT_COST - 1));
+
+        ctx.block_timestamp = ctr.finish_time * 1000000000; // in nano
+        ctx.predecessor_account_id = sbt_registry();
+        testing_env!(ctx);
+
+        assert!(ctr.has_voted_on_all_proposals(alice()));
+        ctr.unbond(alice(), mk_human_sbt(1), Value::String("".to_string()));
+        assert_eq!(
+            test_utils::get_logs(),
+            vec!["Bond doesn't cover the mint cost. Skipping I Voted SBT mint."]
+        );
+        assert_eq!(ctr.bonded_amounts.get(&ALICE_SBT), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn blank_ballots() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        // quorum = 2, min_candidate_support = 2
+        let prop_id = mk_proposal(&mut ctr);
+        ctx.block_timestamp = (START + 2) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.blank_ballots(prop_id), 0);
+
+        let votes = [
+            (alice(), vec![candidate(1)]),
+            (bob(), vec![]),
+            (charlie(), vec![]),
+        ];
+        for (i, (voter, vote)) in votes.into_iter().enumerate() {
+            let token_id = i as u64 + 1;
+            bond_amount_call(&mut ctx, &mut ctr, voter.clone(), token_id);
+            let res = ctr.on_vote_verified(
+                mk_human_sbt(token_id),
+                Some(AccountFlag::Verified),
+                prop_id,
+                voter,
+                vote,
+            );
+            assert!(res.is_ok(), "expected OK, got: {:?}", res);
+        }
+        assert_eq!(ctr.blank_ballots(prop_id), 2);
+        let p = ctr._proposal(prop_id);
+        assert_eq!(p.voters_num, 3);
+        assert_eq!(p.result, vec![1, 0, 0]);
+
+        // revoking a blank ballot
+        match ctr.on_revoke_verified(AccountFlag::Blacklisted, prop_id, bob()) {
+            Ok(_) => (),
+            x => panic!("expected OK, got: {:?}", x),
+        }
+        assert_eq!(ctr.blank_ballots(prop_id), 1);
+
+        // blank ballots count toward the quorum, but no candidate reached the min support
+        ctx.block_timestamp = (ctr.finish_time + 1) * MSECOND;
+        testing_env!(ctx);
+        let p = ctr._proposal(prop_id);
+        assert_eq!(p.voters_num, 2);
+        assert!(p.voters_num >= p.quorum);
+        assert_eq!(
+            ctr.winners_by_proposal(prop_id, None),
+            Vec::<AccountId>::new()
+        );
+    }
+
     #[test]
     fn revoke_vote() {
         let (mut ctx, mut ctr) = setup(&admin());
@@ -1752,6 +3446,98 @@ mod unit_tests {
         );
     }
 
+    #[test]
+    fn winners_cancelled_proposal() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mock_proposal_and_votes(&mut ctx, &mut ctr, 8, 6);
+        ctr.admin_cancel_proposal(prop_id);
+
+        ctx.block_timestamp = (ctr.finish_time + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.winners_by_proposal(prop_id, None), vec![]);
+        assert_eq!(ctr.winners_by_proposal(prop_id, Some(true)), vec![]);
+        let res = ctr.proposal_winners_json(prop_id).unwrap();
+        assert_eq!(res.winners, vec![]);
+        assert!(!res.finalized);
+    }
+
+    #[test]
+    fn proposal_winners_json() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_no_quorum = mk_proposal(&mut ctr);
+        let prop_id = mock_proposal_and_votes(&mut ctx, &mut ctr, 8, 6);
+        assert_eq!(ctr.proposal_winners_json(prop_id + 1), None);
+
+        let winners = vec![
+            (candidate(3), 15),
+            (candidate(6), 11),
+            (candidate(2), 10),
+            (candidate(4), 10),
+        ];
+        // elections not over yet: winners are not returned
+        assert_eq!(
+            ctr.proposal_winners_json(prop_id),
+            Some(ElectionResult {
+                prop_id,
+                typ: ProposalType::HouseOfMerit,
+                seats: 8,
+                winners: vec![],
+                quorum_met: true,
+                finalized: false,
+            })
+        );
+
+        ctx.block_timestamp = (ctr.finish_time + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(
+            ctr.proposal_winners_json(prop_id),
+            Some(ElectionResult {
+                prop_id,
+                typ: ProposalType::HouseOfMerit,
+                seats: 8,
+                winners,
+                quorum_met: true,
+                finalized: true,
+            })
+        );
+        let winners = ctr.proposal_winners_json(prop_id).unwrap().winners;
+        assert_eq!(
+            winners.into_iter().map(|(c, _)| c).collect::<Vec<_>>(),
+            ctr.winners_by_proposal(prop_id, None)
+        );
+
+        // quorum not met
+        let res = ctr.proposal_winners_json(prop_no_quorum).unwrap();
+        assert!(!res.quorum_met);
+        assert!(!res.finalized);
+        assert_eq!(res.winners, vec![]);
+    }
+
+    #[test]
+    fn candidate_rank() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mock_proposal_and_votes(&mut ctx, &mut ctr, 8, 6);
+
+        let rank = |ctr: &Contract, c: u32| ctr.candidate_rank(prop_id, candidate(c));
+        // candidate(3): 15, candidate(6): 11, candidate(2) and candidate(4): 10,
+        // candidate(1) and candidate(5): 5, other candidates: 0
+        assert_eq!(rank(&ctr, 3), Some(1));
+        assert_eq!(rank(&ctr, 6), Some(2));
+        assert_eq!(rank(&ctr, 2), Some(3));
+        assert_eq!(rank(&ctr, 4), Some(3));
+        assert_eq!(rank(&ctr, 1), Some(5));
+        assert_eq!(rank(&ctr, 5), Some(5));
+        assert_eq!(rank(&ctr, 0), Some(7));
+        assert_eq!(rank(&ctr, 99), Some(7));
+        assert_eq!(ctr.candidate_rank(prop_id, alice()), None);
+
+        // disqualified candidates are not ranked
+        ctr.admin_disqualify_candidates(vec![candidate(6)]);
+        assert_eq!(rank(&ctr, 6), None);
+        assert_eq!(rank(&ctr, 2), Some(2));
+        assert_eq!(rank(&ctr, 1), Some(4));
+    }
+
     #[test]
     fn winners_by_proposal_tie() {
         let (mut ctx, mut ctr) = setup(&admin());
@@ -1780,6 +3566,55 @@ mod unit_tests {
         assert_eq!(ctr.winners_by_proposal(prop_id4, None), all[0..4]);
     }
 
+    #[test]
+    fn has_tie_at_boundary() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        // results: c3: 15, c6: 11, c2: 10, c4: 10, c1: 5, c5: 5
+        let prop_no_tie = mock_proposal_and_votes(&mut ctx, &mut ctr, 2, 0);
+        let prop_tie = mock_proposal_and_votes(&mut ctx, &mut ctr, 3, 0);
+        let prop_tail_tie = mock_proposal_and_votes(&mut ctx, &mut ctr, 5, 0);
+        // tie below the min candidate support
+        let prop_tie_no_support = mock_proposal_and_votes(&mut ctx, &mut ctr, 3, 11);
+        let prop_all = mock_proposal_and_votes(&mut ctx, &mut ctr, 6, 0);
+
+        // cooldown not over
+        assert!(!ctr.has_tie_at_boundary(prop_tie));
+
+        ctx.block_timestamp = (START + 111) * MSECOND; // past cooldown
+        testing_env!(ctx);
+        assert!(!ctr.has_tie_at_boundary(prop_no_tie));
+        assert!(ctr.has_tie_at_boundary(prop_tie));
+        assert!(ctr.has_tie_at_boundary(prop_tail_tie));
+        assert!(!ctr.has_tie_at_boundary(prop_tie_no_support));
+        assert!(!ctr.has_tie_at_boundary(prop_all));
+        // the tied candidates are not winners
+        assert_eq!(
+            ctr.winners_by_proposal(prop_tie, None),
+            vec![candidate(3), candidate(6)]
+        );
+
+        // disqualifying one of the tied candidates resolves the tie
+        ctr.admin_disqualify_candidates(vec![candidate(4)]);
+        assert!(!ctr.has_tie_at_boundary(prop_tie));
+    }
+
+    #[test]
+    fn bond_required() {
+        let (_, ctr) = setup(&alice());
+        assert_eq!(
+            ctr.bond_required(Some(AccountFlag::Verified)),
+            U128(BOND_AMOUNT)
+        );
+        assert_eq!(ctr.bond_required(None), U128(GRAY_BOND_AMOUNT));
+    }
+
+    #[test]
+    #[should_panic(expected = "user is blacklisted")]
+    fn bond_required_blacklisted() {
+        let (_, ctr) = setup(&alice());
+        ctr.bond_required(Some(AccountFlag::Blacklisted));
+    }
+
     #[test]
     fn double_bond() {
         let (mut ctx, mut ctr) = setup(&alice());
@@ -1794,6 +3629,24 @@ mod unit_tests {
         assert_eq!(ctr.bonded_amounts.get(&2).unwrap(), 2 * BOND_AMOUNT);
     }
 
+    #[test]
+    fn double_bond_smaller_deposit() {
+        let (mut ctx, mut ctr) = setup(&alice());
+
+        ctx.predecessor_account_id = sbt_registry();
+        ctx.attached_deposit = BOND_AMOUNT;
+        testing_env!(ctx.clone());
+        ctr.bond(alice(), mk_human_sbt(2), Value::String("".to_string()));
+
+        // second bond with a smaller deposit must not reduce the recorded bond
+        ctx.attached_deposit = 1;
+        testing_env!(ctx);
+        ctr.bond(alice(), mk_human_sbt(2), Value::String("".to_string()));
+
+        assert_eq!(ctr.bonded_amounts.get(&2).unwrap(), BOND_AMOUNT + 1);
+        assert_eq!(ctr.bond_by_sbt(2), BOND_AMOUNT + 1);
+    }
+
     #[test]
     #[should_panic(expected = "not an admin")]
     fn admin_disqualify_candidates_not_admin() {
@@ -1812,6 +3665,56 @@ mod unit_tests {
         assert!(res.contains(&disqualified_candidates[1]));
     }
 
+    #[test]
+    fn admin_disqualify_candidates_duplicates() {
+        let (_, mut ctr) = setup(&admin());
+        ctr.admin_disqualify_candidates(vec![candidate(1), candidate(1), candidate(2)]);
+        let mut res = ctr.disqualified_candidates();
+        res.sort();
+        assert_eq!(res, vec![candidate(1), candidate(2)]);
+
+        // the new list replaces the existing one
+        ctr.admin_disqualify_candidates(vec![candidate(2), candidate(3)]);
+        let mut res = ctr.disqualified_candidates();
+        res.sort();
+        assert_eq!(res, vec![candidate(2), candidate(3)]);
+
+        let logs = test_utils::get_logs();
+        assert_eq!(
+            logs,
+            vec![
+                r#"EVENT_JSON:{"standard":"ndc-elections","version":"1.0.0","event":"candidates_disqualified","data":{"accounts":["candidate1.near","candidate2.near"]}}"#,
+                r#"EVENT_JSON:{"standard":"ndc-elections","version":"1.0.0","event":"candidates_disqualified","data":{"accounts":["candidate2.near","candidate3.near"]}}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn admin_requalify_candidate() {
+        let (_, mut ctr) = setup(&admin());
+        ctr.admin_disqualify_candidates(vec![candidate(1), candidate(2)]);
+        assert!(ctr.admin_requalify_candidate(candidate(1)));
+        assert_eq!(ctr.disqualified_candidates(), vec![candidate(2)]);
+        // not disqualified
+        assert!(!ctr.admin_requalify_candidate(candidate(1)));
+        assert!(!ctr.admin_requalify_candidate(candidate(3)));
+        assert_eq!(ctr.disqualified_candidates(), vec![candidate(2)]);
+
+        let logs = test_utils::get_logs();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(
+            logs[1],
+            r#"EVENT_JSON:{"standard":"ndc-elections","version":"1.0.0","event":"candidate_requalified","data":{"account":"candidate1.near"}}"#
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "not an admin")]
+    fn admin_requalify_candidate_not_admin() {
+        let (_, mut ctr) = setup(&alice());
+        ctr.admin_requalify_candidate(candidate(1));
+    }
+
     #[test]
     fn winners_by_proposal_disqualified_candidates() {
         let (mut ctx, mut ctr) = setup(&admin());
@@ -1833,6 +3736,17 @@ mod unit_tests {
         );
     }
 
+    #[test]
+    fn winners_by_proposal_all_disqualified() {
+        let (mut ctx, mut ctr) = setup(&admin());
+        let prop_id = mk_proposal(&mut ctr);
+        ctr.admin_disqualify_candidates(ctr._proposal(prop_id).candidates);
+
+        ctx.block_timestamp = (START + 2) * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(ctr.winners_by_proposal(prop_id, Some(true)), vec![]);
+    }
+
     #[test]
     #[should_panic(expected = "not an admin")]
     fn admin_mint_sbt_not_admin() {
@@ -13,6 +13,7 @@ pub struct OldState {
     pub authority: AccountId,
     pub sbt_registry: AccountId,
     pub disqualified_candidates: LazyOption<HashSet<AccountId>>,
+    pub class_metadata: LookupMap<ClassId, ClassMetadata>,
 }
 
 #[near_bindgen]
@@ -23,7 +24,18 @@ impl Contract {
     pub fn migrate() -> Self {
         let old_state: OldState = env::state_read().expect("failed");
         // new field in the smart contract :
-        // + class_metadata: ClassMetadata,
+        // + cancelled_proposals: LookupSet<u32>,
+        // + min_start_lead_ms: u64,
+        // + bonded_count: u32,
+        // + policy_count: u32,
+        // + blank_ballots: LookupMap<u32, u64>,
+        // + vote_change_allowed: LookupSet<u32>,
+        // + post_finish_authority: Option<AccountId>,
+        // + policy_accept_count: LookupMap<(u64, [u8; 32]), u64>,
+        // + policy_epoch: u64,
+        // + accepted_policy_epoch: LookupMap<AccountId, u64>,
+        // NOTE: the counters start from zero, so bonds and policy acceptances recorded before
+        // the migration are not counted.
 
         Self {
             pause: old_state.pause,
@@ -37,7 +49,18 @@ impl Contract {
             authority: old_state.authority,
             sbt_registry: old_state.sbt_registry,
             disqualified_candidates: old_state.disqualified_candidates,
-            class_metadata: LookupMap::new(StorageKey::ClassMetadata),
+            class_metadata: old_state.class_metadata,
+            cancelled_proposals: LookupSet::new(StorageKey::CancelledProposals),
+            min_start_lead_ms: 0,
+            bonded_count: 0,
+            policy_count: 0,
+            blank_ballots: LookupMap::new(StorageKey::BlankBallots),
+            vote_change_allowed: LookupSet::new(StorageKey::VoteChangeAllowed),
+            post_finish_authority: None,
+            policy_accept_count: LookupMap::new(StorageKey::PolicyAcceptCount),
+            // existing acceptances have no epoch recorded, so they are valid in the epoch 0
+            policy_epoch: 0,
+            accepted_policy_epoch: LookupMap::new(StorageKey::AcceptedPolicyEpoch),
         }
     }
 }
@@ -21,12 +21,15 @@ pub enum ProposalType {
 #[derive(Serialize)]
 #[serde(crate = "near_sdk::serde")]
 #[cfg_attr(test, derive(Debug, PartialEq))]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize))]
 pub enum ProposalStatus {
     #[allow(non_camel_case_types)]
     NOT_STARTED,
     ONGOING,
     COOLDOWN,
     ENDED,
+    /// proposal was cancelled by the admin
+    CANCELLED,
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -41,7 +44,8 @@ pub struct Proposal {
     /// duration of cooldown after the proposal ends. During this time votes cannot be submitted and
     /// the malicious votes can be revoked by authorities (in milliseconds).
     pub cooldown: u64,
-    /// min amount of voters to legitimize the voting.
+    /// min amount of voters to legitimize the voting. Blank ballots (empty votes) count
+    /// toward the quorum.
     pub quorum: u32,
     /// max amount of seats a voter can allocate candidates for. (The number of `seats` equals the number of vote credits)
     pub seats: u16,
@@ -53,7 +57,8 @@ pub struct Proposal {
     /// map of voter SBT -> candidates they voted for (token IDs used for voting -> candidates index)
     pub voters: LookupMap<TokenId, Vec<usize>>,
     pub voters_num: u32,
-    /// min amount of votes for a candidate to be considered a "winner".
+    /// min amount of votes for a candidate to be considered a "winner". Blank ballots
+    /// don't support any candidate.
     pub min_candidate_support: u64,
     /// Map of user -> sbt they voted with
     pub user_sbt: LookupMap<AccountId, TokenId>,
@@ -80,10 +85,31 @@ pub struct ProposalView {
     pub seats: u16,
     /// list of candidates with sum of votes.
     pub result: Vec<(AccountId, u64)>,
+    /// status computed at the query time.
+    pub status: ProposalStatus,
+}
+
+/// Structured election result of a proposal, for integrations.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize))]
+pub struct ElectionResult {
+    pub prop_id: u32,
+    pub typ: ProposalType,
+    pub seats: u16,
+    /// winners with their number of votes, sorted by votes in descending order. Empty if the
+    /// result is not `finalized`.
+    pub winners: Vec<(AccountId, u64)>,
+    /// true if the number of voters reached the proposal quorum.
+    pub quorum_met: bool,
+    /// true if the proposal is past the cooldown and the `finish_time`, reached the quorum
+    /// and is not cancelled.
+    pub finalized: bool,
 }
 
 impl Proposal {
-    pub fn to_view(self, id: u32) -> ProposalView {
+    pub fn to_view(self, id: u32, status: ProposalStatus) -> ProposalView {
         let mut result: Vec<(AccountId, u64)> = Vec::with_capacity(self.candidates.len());
         for i in 0..self.candidates.len() {
             let c = self.candidates[i].clone();
@@ -101,6 +127,7 @@ impl Proposal {
             voters_num: self.voters_num,
             seats: self.seats,
             result,
+            status,
         }
     }
 
@@ -126,29 +153,42 @@ impl Proposal {
     }
 
     /// once vote proof has been verified, we call this function to register a vote.
+    /// If `allow_change` is true, a token which already voted replaces its previous vote:
+    /// the previous selections are removed from the `result` and `voters_num` doesn't change.
+    /// Returns the previous vote (candidate indexes) if it was replaced.
     pub fn vote_on_verified(
         &mut self,
         sbts: &Vec<TokenId>,
         voter: AccountId,
         vote: Vote,
-    ) -> Result<(), VoteError> {
+        allow_change: bool,
+    ) -> Result<Option<Vec<usize>>, VoteError> {
         self.assert_active();
         let mut indexes = Vec::new();
-        self.voters_num += 1;
         for candidate in vote {
             let idx = self.candidates.binary_search(&candidate).unwrap();
             self.result[idx] += 1;
             indexes.push(idx);
         }
+        let mut previous = None;
         // TODO: this logic needs to be updated once we use more tokens per user to vote
-        // now we require that sbts length is 1 (it's checked in the contract.on_vote_verified)
+        // now contract.on_vote_verified only passes the single token used for bonding
         for t in sbts {
-            if self.voters.insert(t, &indexes).is_some() {
-                return Err(VoteError::DoubleVote(*t));
+            if let Some(prev) = self.voters.insert(t, &indexes) {
+                if !allow_change {
+                    return Err(VoteError::DoubleVote(*t));
+                }
+                for idx in prev.iter() {
+                    self.result[*idx] -= 1;
+                }
+                previous = Some(prev);
             }
             self.user_sbt.insert(&voter, t);
         }
-        Ok(())
+        if previous.is_none() {
+            self.voters_num += 1;
+        }
+        Ok(previous)
     }
 
     pub fn revoke_votes(&mut self, token_id: TokenId) -> Result<(), RevokeVoteError> {
@@ -184,6 +224,15 @@ impl Proposal {
 
 pub type Vote = Vec<AccountId>;
 
+/// Payload of the `vote` call, passed through the registry `is_human_call_lock`.
+#[derive(Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Serialize))]
+pub struct VotePayload {
+    pub prop_id: u32,
+    pub vote: Vote,
+}
+
 /// * valid_candidates must be a sorted slice.
 pub fn validate_vote(
     typ: ProposalType,
@@ -222,7 +271,7 @@ pub fn assert_hash_hex_string(s: &str) -> [u8; 32] {
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod unit_tests {
     use super::*;
-    use crate::{storage::StorageKey, ProposalType, ProposalView};
+    use crate::{storage::StorageKey, ProposalStatus, ProposalType, ProposalView};
 
     fn mk_account(i: u16) -> AccountId {
         AccountId::new_unchecked(format!("acc{}", i))
@@ -277,8 +326,9 @@ mod unit_tests {
                     (mk_account(3), 321),
                     (mk_account(4), 121)
                 ],
+                status: ProposalStatus::ONGOING,
             },
-            p.to_view(12)
+            p.to_view(12, ProposalStatus::ONGOING)
         )
     }
 
@@ -12,6 +12,11 @@ pub enum StorageKey {
     UserSBT(u32),
     DisqualifiedCandidates,
     ClassMetadata,
+    CancelledProposals,
+    BlankBallots,
+    VoteChangeAllowed,
+    PolicyAcceptCount,
+    AcceptedPolicyEpoch,
 }
 
 #[derive(PartialEq, Deserialize)]
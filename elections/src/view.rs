@@ -1,8 +1,9 @@
-use near_sdk::{env, near_bindgen, AccountId, Balance};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near_bindgen, AccountId, Balance, FunctionError};
 use sbt::{ClassId, ClassMetadata};
 use uint::hex;
 
-use crate::{proposal::*, TokenId};
+use crate::{bond_for_flag, proposal::*, AccountFlag, TokenId, MINT_COST, VOTE_GAS};
 use crate::{Contract, ContractExt};
 
 #[near_bindgen]
@@ -19,24 +20,93 @@ impl Contract {
         self.finish_time
     }
 
+    /// Returns true if all proposals ended (current time is past the `finish_time`), so users
+    /// can unbond.
+    pub fn is_election_over(&self) -> bool {
+        env::block_timestamp_ms() > self.finish_time
+    }
+
+    /// Returns the number of eligible voters: accounts which must both bond and accept the
+    /// Fair Voting Policy to vote. Bonds are tracked per IAH token and policy acceptance per
+    /// account (only in the current policy epoch), so this returns the smaller of the two
+    /// counters.
+    pub fn eligible_voter_count(&self) -> u32 {
+        std::cmp::min(self.bonded_count, self.policy_count)
+    }
+
+    /// Returns the number of blank ballots (empty votes) cast for the proposal. Blank ballots
+    /// count toward the proposal quorum, but not toward the candidates `min_candidate_support`.
+    pub fn blank_ballots(&self, prop_id: u32) -> u64 {
+        self.blank_ballots.get(&prop_id).unwrap_or(0)
+    }
+
+    /// Returns true if voters can change their vote for the given proposal.
+    pub fn vote_change_allowed(&self, prop_id: u32) -> bool {
+        self.vote_change_allowed.contains(&prop_id)
+    }
+
+    /// Returns the total number of votes cast across all proposals (sum of the proposals
+    /// `voters_num`). Revoked votes are not counted.
+    pub fn total_votes_cast(&self) -> u64 {
+        (1..=self.prop_counter)
+            .filter_map(|id| self.proposals.get(&id))
+            .map(|p| p.voters_num as u64)
+            .sum()
+    }
+
+    /// Returns the account which can call admin methods after the `finish_time`, if set.
+    pub fn post_finish_authority(&self) -> Option<AccountId> {
+        self.post_finish_authority.clone()
+    }
+
+    /// Returns the minimum amount of gas which must be attached to the `vote` call.
+    pub fn vote_gas_requirement(&self) -> U64 {
+        U64(VOTE_GAS.0)
+    }
+
     pub fn proposals(&self) -> Vec<ProposalView> {
         let mut proposals = Vec::with_capacity(self.prop_counter as usize);
         for i in 1..=self.prop_counter {
-            proposals.push(self.proposals.get(&i).unwrap().to_view(i));
+            let p = self.proposals.get(&i).unwrap();
+            let status = self._proposal_status(i, &p);
+            proposals.push(p.to_view(i, status));
         }
         proposals
     }
 
+    /// Returns the proposal, including its status computed at the query time.
     pub fn proposal(&self, prop_id: u32) -> ProposalView {
-        self._proposal(prop_id).to_view(prop_id)
+        let p = self._proposal(prop_id);
+        let status = self._proposal_status(prop_id, &p);
+        p.to_view(prop_id, status)
+    }
+
+    /// Returns the proposal reference link, or None if the proposal doesn't exist.
+    pub fn ref_link(&self, prop_id: u32) -> Option<String> {
+        self.proposals.get(&prop_id).map(|p| p.ref_link)
+    }
+
+    /// Returns the proposal ballot parameters: `(seats, min_candidate_support, quorum)`, or
+    /// None if the proposal doesn't exist.
+    pub fn proposal_params(&self, prop_id: u32) -> Option<(u16, u64, u32)> {
+        self.proposals
+            .get(&prop_id)
+            .map(|p| (p.seats, p.min_candidate_support, p.quorum))
     }
 
     /// Returns the proposal status
     pub fn proposal_status(&self, prop_id: u32) -> Option<ProposalStatus> {
-        let now = env::block_timestamp_ms();
         self.proposals
             .get(&prop_id)
-            .map(|p| p.status(now, self.finish_time))
+            .map(|p| self._proposal_status(prop_id, &p))
+    }
+
+    fn _proposal_status(&self, prop_id: u32, p: &Proposal) -> ProposalStatus {
+        if self.cancelled_proposals.contains(&prop_id) {
+            ProposalStatus::CANCELLED
+        } else {
+            p.status(env::block_timestamp_ms(), self.finish_time)
+        }
     }
 
     /// Returns the policy if user has accepted it otherwise returns None
@@ -44,6 +114,17 @@ impl Contract {
         self.accepted_policy.get(&user).map(hex::encode)
     }
 
+    /// Returns the number of accounts which accepted the given policy (hex encoded hash) in the
+    /// given policy `epoch` (defaults to the current epoch). Counters of the previous epochs
+    /// are frozen at `admin_set_policy` and show how many accounts accepted a retired policy.
+    /// NOTE: acceptances recorded before the contract migration are not counted.
+    pub fn accept_count_for_policy(&self, policy_hash: String, epoch: Option<u64>) -> u64 {
+        let policy = assert_hash_hex_string(&policy_hash);
+        self.policy_accept_count
+            .get(&(epoch.unwrap_or(self.policy_epoch), policy))
+            .unwrap_or_default()
+    }
+
     /// Returns all the users votes for all the proposals. If user has not voted yet a vector with None values will be returned.
     /// Eg. if we have 3 porposals and user only voted on first one then the return value will look like [Some([1,2]), None, None]
     /// NOTE: the response may not be consistent with the registry. If user will do a soul_transfer, then technically votes should be associated
@@ -64,14 +145,82 @@ impl Contract {
         to_return
     }
 
-    /// Returns bond amount by SBT TokenID.
+    /// Returns the candidates the given SBT voted for in the proposal. Returns an empty list
+    /// for a blank ballot, and None if the token didn't vote (or the vote was revoked) or the
+    /// proposal doesn't exist.
+    pub fn vote_by_token(&self, prop_id: u32, token_id: TokenId) -> Option<Vec<AccountId>> {
+        let p = self.proposals.get(&prop_id)?;
+        let vote = p.voters.get(&token_id)?;
+        Some(vote.into_iter().map(|i| p.candidates[i].clone()).collect())
+    }
+
+    /// Returns bond amount by SBT TokenID, after the partial slashes. Returns 0 if the token
+    /// didn't bond.
     pub fn bond_by_sbt(&self, sbt: TokenId) -> Balance {
         self.bonded_amounts.get(&sbt).unwrap_or(0)
     }
 
+    /// Same as `bond_by_sbt`, but returns the bond as a JSON string (`U128`).
+    pub fn bond_of(&self, token_id: TokenId) -> U128 {
+        U128(self.bond_by_sbt(token_id))
+    }
+
+    /// Returns ids of the ongoing proposals the given SBT hasn't voted on yet.
+    /// Cancelled proposals are skipped.
+    pub fn pending_proposals_for(&self, token_id: TokenId) -> Vec<u32> {
+        let now = env::block_timestamp_ms();
+        (1..=self.prop_counter)
+            .filter(|id| !self.cancelled_proposals.contains(id))
+            .filter(|id| {
+                self.proposals.get(id).map_or(false, |p| {
+                    matches!(p.status(now, self.finish_time), ProposalStatus::ONGOING)
+                        && !p.voters.contains_key(&token_id)
+                })
+            })
+            .collect()
+    }
+
+    /// Returns true if the given SBT voted on all proposals, hence `unbond` will mint the
+    /// I Voted SBT (provided the bond covers the mint cost). Cancelled proposals are skipped.
+    pub fn will_mint_i_voted(&self, token_id: TokenId) -> bool {
+        (1..=self.prop_counter).all(|id| match self.proposals.get(&id) {
+            Some(p) => p.voters.contains_key(&token_id) || self.cancelled_proposals.contains(&id),
+            None => true,
+        })
+    }
+
+    /// Returns the amount of NEAR the `token_id` owner will get back on `unbond`: the bonded
+    /// amount minus the `I Voted` SBT mint cost if the SBT will be minted, otherwise the full
+    /// bond. Returns 0 if the token didn't bond.
+    pub fn expected_unbond(&self, token_id: TokenId) -> U128 {
+        let bond = self.bonded_amounts.get(&token_id).unwrap_or(0);
+        if bond >= MINT_COST && self.will_mint_i_voted(token_id) {
+            return U128(bond - MINT_COST);
+        }
+        U128(bond)
+    }
+
+    /// Returns the bond amount required to vote by an account with the given IAH registry flag.
+    /// Panics if the account is blacklisted: blacklisted accounts can't vote.
+    pub fn bond_required(&self, flag: Option<AccountFlag>) -> U128 {
+        match bond_for_flag(&flag) {
+            Ok(amount) => U128(amount),
+            Err(err) => err.panic(),
+        }
+    }
+
     /// Returns true if user has voted on all proposals, otherwise false.
+    /// Cancelled proposals are skipped, because they can't be voted on.
     pub fn has_voted_on_all_proposals(&self, user: AccountId) -> bool {
-        self.user_votes(user).iter().all(|vote| vote.is_some())
+        (1..=self.prop_counter)
+            .filter(|id| !self.cancelled_proposals.contains(id))
+            .filter_map(|id| self.proposals.get(&id))
+            .all(|p| {
+                p.user_sbt
+                    .get(&user)
+                    .and_then(|token_id| p.voters.get(&token_id))
+                    .is_some()
+            })
     }
 
     /// Returns the required policy
@@ -79,6 +228,11 @@ impl Contract {
         hex::encode(self.policy)
     }
 
+    /// Returns the current policy epoch, increased on every policy update.
+    pub fn policy_epoch(&self) -> u64 {
+        self.policy_epoch
+    }
+
     /// Returns a list of winners of the proposal if the elections is over and the quorum has been reached, otherwise returns empty list.
     /// A candidate is considered the winner only if he reached the `min_candidate_support`
     /// and is not listed as disqualified.
@@ -86,47 +240,126 @@ impl Contract {
     /// did not reach the required minimum support.
     /// If there is a tie break at the tail and it exceeds the number of seats, the accounts
     /// in tie at the tail are not considered winners.
+    /// Cancelled proposals have no winners.
     pub fn winners_by_proposal(&self, prop_id: u32, ongoing: Option<bool>) -> Vec<AccountId> {
         let proposal = self._proposal(prop_id);
 
-        if !(proposal.is_past_cooldown()
-            && env::block_timestamp_ms() > self.finish_time
-            && proposal.voters_num >= proposal.quorum)
-            && !ongoing.unwrap_or(false)
+        if self.cancelled_proposals.contains(&prop_id)
+            || (!self.is_finalized(&proposal) && !ongoing.unwrap_or(false))
         {
             return Vec::new();
         }
 
-        // Filter and sort the candidates in one step
-        let disqualified = self.disqualified_candidates.get().unwrap_or_default();
-        let mut indexed_results: Vec<(usize, u64)> = proposal
-            .result
-            .iter()
-            .enumerate()
-            .filter(|(idx, _)| !disqualified.contains(&proposal.candidates[*idx]))
-            .map(|(idx, &votes)| (idx, votes))
-            .collect();
+        self.winners_with_votes(&proposal)
+            .into_iter()
+            .map(|(candidate, _)| candidate)
+            .collect()
+    }
 
-        indexed_results.sort_by_key(|&(_, value)| std::cmp::Reverse(value));
+    /// Returns the structured election result of the proposal: winners with their votes
+    /// (see `winners_by_proposal`), whether the quorum was met and whether the result is final.
+    /// Same as `winners_by_proposal`, `winners` is empty until the proposal is finalized.
+    /// Cancelled proposals have no winners and are never finalized.
+    /// Returns None if the proposal doesn't exist.
+    pub fn proposal_winners_json(&self, prop_id: u32) -> Option<ElectionResult> {
+        let proposal = self.proposals.get(&prop_id)?;
+        let finalized =
+            !self.cancelled_proposals.contains(&prop_id) && self.is_finalized(&proposal);
+        Some(ElectionResult {
+            prop_id,
+            winners: if finalized {
+                self.winners_with_votes(&proposal)
+            } else {
+                Vec::new()
+            },
+            quorum_met: proposal.voters_num >= proposal.quorum,
+            finalized,
+            seats: proposal.seats,
+            typ: proposal.typ,
+        })
+    }
+
+    /// Returns true if there is a tie at the seats boundary: the candidate at the last seat
+    /// has the same number of votes (and reached the `min_candidate_support`) as the best
+    /// candidate outside of the seats. Tied candidates are not returned by `winners_by_proposal`,
+    /// so the organizers have to handle the tie explicitly.
+    /// Returns false if the proposal is not finalized (past cooldown and `finish_time`, with
+    /// quorum reached).
+    pub fn has_tie_at_boundary(&self, prop_id: u32) -> bool {
+        let proposal = self._proposal(prop_id);
+        if !self.is_finalized(&proposal) {
+            return false;
+        }
+
+        let seats = proposal.seats as usize;
+        let results = self.sorted_results(&proposal);
+        match (results.get(seats - 1), results.get(seats)) {
+            (Some(&(_, last_in)), Some(&(_, first_out))) => {
+                last_in == first_out && last_in >= proposal.min_candidate_support
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the current 1-based rank of the candidate by the number of votes. Candidates
+    /// with the same number of votes share the rank (eg: 1, 2, 2, 4). Disqualified candidates
+    /// are not ranked.
+    /// Returns None if the candidate is not part of the proposal or is disqualified.
+    /// Panics if the proposal doesn't exist.
+    pub fn candidate_rank(&self, prop_id: u32, candidate: AccountId) -> Option<u32> {
+        let proposal = self._proposal(prop_id);
+        let idx = proposal.candidates.binary_search(&candidate).ok()?;
+        let results = self.sorted_results(&proposal);
+        let votes = results.iter().find(|&&(i, _)| i == idx)?.1;
+        let better = results.iter().filter(|&&(_, v)| v > votes).count() as u32;
+        Some(better + 1)
+    }
 
+    /// Returns true if the proposal is past cooldown and the `finish_time`, and reached the quorum.
+    fn is_finalized(&self, proposal: &Proposal) -> bool {
+        proposal.is_past_cooldown()
+            && env::block_timestamp_ms() > self.finish_time
+            && proposal.voters_num >= proposal.quorum
+    }
+
+    /// Returns winners with their votes, sorted by votes in descending order. See
+    /// `winners_by_proposal` for the winner conditions.
+    fn winners_with_votes(&self, proposal: &Proposal) -> Vec<(AccountId, u64)> {
+        let indexed_results = self.sorted_results(proposal);
         let mut winners = Vec::new();
         let last_out_idx = proposal.seats as usize;
         let last_out_votes = indexed_results
             .get(last_out_idx)
             .map(|&(_, votes)| votes)
-            .unwrap_or(indexed_results[0].1 + 1);
+            .unwrap_or_else(|| indexed_results.first().map_or(0, |&(_, votes)| votes + 1));
 
         for (idx, votes) in indexed_results.into_iter().take(last_out_idx) {
             // Filter out tie in the tail if it could exceed the seats
             if proposal.min_candidate_support <= votes && last_out_votes < votes {
                 let candidate = proposal.candidates.get(idx).unwrap();
-                winners.push(candidate.clone());
+                winners.push((candidate.clone(), votes));
             }
         }
 
         winners
     }
 
+    /// Returns (candidate index, votes) pairs of not disqualified candidates, sorted by votes
+    /// in descending order.
+    fn sorted_results(&self, proposal: &Proposal) -> Vec<(usize, u64)> {
+        let disqualified = self.disqualified_candidates.get().unwrap_or_default();
+        let mut indexed_results: Vec<(usize, u64)> = proposal
+            .result
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !disqualified.contains(&proposal.candidates[*idx]))
+            .map(|(idx, &votes)| (idx, votes))
+            .collect();
+
+        indexed_results.sort_by_key(|&(_, value)| std::cmp::Reverse(value));
+        indexed_results
+    }
+
     /// Returns the list of disqualified candidates
     pub fn disqualified_candidates(&self) -> Vec<AccountId> {
         self.disqualified_candidates
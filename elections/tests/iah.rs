@@ -1,6 +1,8 @@
 use integrations::setup_registry;
 use near_units::parse_near;
-use near_workspaces::{Account, AccountId, Contract, DevNetwork, Worker};
+use near_workspaces::{
+    result::ExecutionFinalResult, Account, AccountId, Contract, DevNetwork, Worker,
+};
 use sbt::ClassMetadata;
 use serde_json::json;
 
@@ -54,6 +56,7 @@ async fn init(worker: &Worker<impl DevNetwork>) -> anyhow::Result<InitStruct> {
             "sbt_registry": registry_contract.id(),
             "policy": policy1(),
             "finish_time": 1,
+            "min_start_lead_ms": 0,
             "class_metadata": ClassMetadata { name: "I Voted SBT".to_string(), symbol: None, icon: None, reference: None, reference_hash: None},
         }))
         .max_gas()
@@ -168,13 +171,7 @@ async fn vote_by_human() -> anyhow::Result<()> {
     // fast forward to the voting period
     worker.fast_forward(10).await?;
 
-    let res = setup
-        .alice
-        .call(setup.ndc_elections_contract.id(), "vote")
-        .args_json(json!({"prop_id": setup.proposal_id, "vote": [setup.john.id()],}))
-        .max_gas()
-        .transact()
-        .await?;
+    let res = vote(&setup, &setup.alice, setup.john.id()).await?;
     assert!(res.is_success(), "{:?}", res);
 
     Ok(())
@@ -189,19 +186,11 @@ async fn vote_by_non_human() -> anyhow::Result<()> {
     // fast forward to the voting period
     worker.fast_forward(12).await?;
 
-    let res = non_human
-        .call(setup.ndc_elections_contract.id(), "vote")
-        .args_json(json!({"prop_id": setup.proposal_id, "vote": [setup.john.id()],}))
-        .max_gas()
-        .transact()
-        .await?;
+    // the registry rejects `is_human_call_lock` from an account without IAH SBT
+    let res = vote(&setup, &non_human, setup.john.id()).await?;
     assert!(res.is_failure(), "resp should be a failure {:?}", res);
-    let res_str = format!("{:?}", res);
-    assert!(
-        res_str.contains("user didn't accept the voting policy"),
-        "{}",
-        res_str
-    );
+    let failures = format!("{:?}", res.receipt_failures());
+    assert!(failures.contains("caller not a human"), "{}", failures);
 
     Ok(())
 }
@@ -214,20 +203,11 @@ async fn vote_expired_iah_token() -> anyhow::Result<()> {
     // fast forward to the voting period
     worker.fast_forward(70).await?;
 
-    let res = setup
-        .john
-        .call(setup.ndc_elections_contract.id(), "vote")
-        .args_json(json!({"prop_id": setup.proposal_id, "vote": [setup.alice.id()],}))
-        .max_gas()
-        .transact()
-        .await?;
+    // the registry rejects `is_human_call_lock` once the IAH SBT expired
+    let res = vote(&setup, &setup.john, setup.alice.id()).await?;
     assert!(res.is_failure(), "resp should be a failure {:?}", res);
     let failures = format!("{:?}", res.receipt_failures());
-    assert!(
-        failures.contains("voter is not a verified human"),
-        "{}",
-        failures
-    );
+    assert!(failures.contains("caller not a human"), "{}", failures);
 
     Ok(())
 }
@@ -236,16 +216,11 @@ async fn vote_expired_iah_token() -> anyhow::Result<()> {
 async fn vote_without_accepting_policy() -> anyhow::Result<()> {
     let worker = near_workspaces::sandbox().await?;
     let setup = init(&worker).await?;
-    let zen_acc = worker.dev_create_account().await?;
     // fast forward to the voting period
     worker.fast_forward(10).await?;
 
-    let res = zen_acc
-        .call(setup.ndc_elections_contract.id(), "vote")
-        .args_json(json!({"prop_id": setup.proposal_id, "vote": [setup.john.id()],}))
-        .max_gas()
-        .transact()
-        .await?;
+    // bob is a human, but didn't accept the policy
+    let res = vote(&setup, &setup.bob, setup.john.id()).await?;
     assert!(res.is_failure(), "resp should be a failure {:?}", res);
     let failures = format!("{:?}", res.receipt_failures());
     assert!(
@@ -281,13 +256,7 @@ async fn vote_without_deposit_bond() -> anyhow::Result<()> {
     // fast forward to the voting period
     worker.fast_forward(10).await?;
 
-    let res = setup
-        .bob
-        .call(setup.ndc_elections_contract.id(), "vote")
-        .args_json(json!({"prop_id": setup.proposal_id, "vote": [setup.john.id()],}))
-        .max_gas()
-        .transact()
-        .await?;
+    let res = vote(&setup, &setup.bob, setup.john.id()).await?;
     assert!(res.is_failure(), "resp should be a failure {:?}", res);
     let failures = format!("{:?}", res.receipt_failures());
     assert!(failures.contains("Voter didn't bond"), "{}", failures);
@@ -303,13 +272,7 @@ async fn unbond_amount_before_election_end() -> anyhow::Result<()> {
     // fast forward to the voting period
     worker.fast_forward(12).await?;
 
-    let res = setup
-        .alice
-        .call(setup.ndc_elections_contract.id(), "vote")
-        .args_json(json!({"prop_id": setup.proposal_id, "vote": [setup.john.id()],}))
-        .max_gas()
-        .transact()
-        .await?;
+    let res = vote(&setup, &setup.alice, setup.john.id()).await?;
     assert!(res.is_success(), "{:?}", res);
 
     let res1 = setup.alice
@@ -338,13 +301,7 @@ async fn unbond_amount() -> anyhow::Result<()> {
     // fast forward to the voting period
     worker.fast_forward(12).await?;
 
-    let res = setup
-        .alice
-        .call(setup.ndc_elections_contract.id(), "vote")
-        .args_json(json!({"prop_id": setup.proposal_id, "vote": [setup.john.id()],}))
-        .max_gas()
-        .transact()
-        .await?;
+    let res = vote(&setup, &setup.alice, setup.john.id()).await?;
     assert!(res.is_success(), "{:?}", res);
 
     let balance_before = setup.alice.view_account().await?;
@@ -406,13 +363,7 @@ async fn sbt_mint_no_vote() -> anyhow::Result<()> {
     worker.fast_forward(12).await?;
 
     // Vote only on one proposal
-    let res = setup
-        .alice
-        .call(setup.ndc_elections_contract.id(), "vote")
-        .args_json(json!({"prop_id": setup.proposal_id, "vote": [setup.john.id()],}))
-        .max_gas()
-        .transact()
-        .await?;
+    let res = vote(&setup, &setup.alice, setup.john.id()).await?;
     assert!(res.is_success(), "{:?}", res);
 
     let balance_before = setup.alice.view_account().await?;
@@ -490,13 +441,7 @@ async fn state_change() -> anyhow::Result<()> {
         .json::<ProposalView>()?;
     assert_eq!(proposal.voters_num, 0);
 
-    let res = setup
-        .alice
-        .call(setup.ndc_elections_contract.id(), "vote")
-        .args_json(json!({"prop_id": setup.proposal_id, "vote": [setup.john.id()],}))
-        .max_gas()
-        .transact()
-        .await?;
+    let res = vote(&setup, &setup.alice, setup.john.id()).await?;
     assert!(res.is_success(), "{:?}", res);
 
     let proposal = setup
@@ -522,13 +467,7 @@ async fn revoke_vote() -> anyhow::Result<()> {
     worker.fast_forward(10).await?;
 
     // alice votes
-    let res = setup
-        .alice
-        .call(setup.ndc_elections_contract.id(), "vote")
-        .args_json(json!({"prop_id": setup.proposal_id, "vote": [setup.john.id()],}))
-        .max_gas()
-        .transact()
-        .await?;
+    let res = vote(&setup, &setup.alice, setup.john.id()).await?;
     assert!(res.is_success(), "{:?}", res.receipt_failures());
 
     // try to revoke the vote (alice is not blacklisted)
@@ -578,7 +517,8 @@ async fn migration_mainnet() -> anyhow::Result<()> {
     let admin = worker_sandbox.dev_create_account().await?;
     let registry = worker_sandbox.dev_create_account().await?;
 
-    // init the contract
+    // init the contract with the mainnet `new` signature. New fields (eg `min_start_lead_ms`)
+    // are set by the migration.
     let res = elections
         .call("new")
         .args_json(json!({
@@ -631,6 +571,23 @@ async fn migration_mainnet() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Votes through the registry `is_human_call_lock`, locking the user IAH SBT until the proposal
+/// end.
+async fn vote(
+    setup: &InitStruct,
+    user: &Account,
+    candidate: &AccountId,
+) -> anyhow::Result<ExecutionFinalResult> {
+    let payload = json!({"prop_id": setup.proposal_id, "vote": [candidate]}).to_string();
+    let res = user
+        .call(setup.registry_contract.id(), "is_human_call_lock")
+        .args_json(json!({"ctr": setup.ndc_elections_contract.id(), "function": "vote", "payload": payload, "lock_duration": 200 * 1000, "with_proof": false}))
+        .max_gas()
+        .transact()
+        .await?;
+    Ok(res)
+}
+
 async fn accept_policy_and_bond(
     registry: Contract,
     election: Contract,
@@ -71,7 +71,8 @@ pub async fn instantiate_congress(
             "hook_auth": hook_auth,
             "budget_cap": parse_near!("1 N").to_string(),
             "big_funding_threshold": parse_near!("0.3 N").to_string(),
-            "registry": registry
+            "registry": registry,
+            "min_participation": 0
         }))
         .max_gas()
         .transact()
@@ -6,10 +6,18 @@ pub const SLASH_REWARD: Balance = 900 * MILI_NEAR;
 
 /// Gas reserved for final failure callback which panics if one of the callback fails.
 pub const FAILURE_CALLBACK_GAS: Gas = Gas(3 * Gas::ONE_TERA.0);
+/// Default gas for the `on_execute` callback, see `Contract::execute_callback_gas`.
 pub const EXECUTE_CALLBACK_GAS: Gas = Gas(4 * Gas::ONE_TERA.0);
+/// Upper bound for the configurable `on_execute` callback gas.
+pub const MAX_EXECUTE_CALLBACK_GAS: Gas = Gas(20 * Gas::ONE_TERA.0);
 
+/// Gas required by the `execute` call itself. `FunctionCall` proposal execution additionally
+/// requires gas for all actions and the configurable `Contract::execute_callback_gas`.
 pub const EXECUTE_GAS: Gas = Gas(8 * Gas::ONE_TERA.0);
 
+/// Max number of actions in a FunctionCall proposal, to not exceed the execution gas.
+pub const MAX_ACTIONS: usize = 10;
+
 // 64bytes(accountID) + 1byte (prefix) + 4bytes(proposal_id) + vote(byte) = 72B -> add 20% margin = < 90B
 pub const VOTE_STORAGE: u64 = 90;
 
@@ -79,6 +79,9 @@ pub enum PrevoteError {
     NotCongressMember,
     LockedUntil,
     NotIAHreg,
+    NotProposer,
+    AlreadyActive,
+    Overdue,
 }
 
 impl FunctionError for PrevoteError {
@@ -94,6 +97,9 @@ impl FunctionError for PrevoteError {
                 panic_str("account must be locked in iah_registry longer than the prevote end")
             }
             PrevoteError::NotIAHreg => panic_str("must be called by iah_registry"),
+            PrevoteError::NotProposer => panic_str("only the proposer can cancel the proposal"),
+            PrevoteError::AlreadyActive => panic_str("proposal is already in the active queue"),
+            PrevoteError::Overdue => panic_str("proposal is overdue and can only be slashed"),
         }
     }
 }
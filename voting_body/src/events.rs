@@ -38,6 +38,14 @@ pub(crate) fn emit_prevote_prop_slashed(prop_id: u32, bond: Balance) {
     });
 }
 
+/// Emitted when the proposer cancels their pre-vote proposal and the bond is refunded.
+pub(crate) fn emit_prevote_prop_cancelled(prop_id: u32, bond: Balance) {
+    emit_event(EventPayload {
+        event: "proposal-prevote-cancelled",
+        data: json!({ "prop_id": prop_id, "bond": U128(bond)}),
+    });
+}
+
 pub(crate) fn emit_prop_slashed(prop_id: u32, bond: Balance) {
     emit_event(EventPayload {
         event: "proposal-slash",
@@ -59,6 +67,33 @@ pub(crate) fn emit_executed(prop_id: u32) {
     });
 }
 
+/// execute-failed event is emitted when the proposal execution promise failed. The proposal
+/// status is set to `Failed` and it can be executed again.
+/// * `kind`: name of the proposal kind.
+pub(crate) fn emit_execute_failed(prop_id: u32, kind: &PropKind) {
+    emit_event(EventPayload {
+        event: "execute-failed",
+        data: json!({ "prop_id": prop_id, "kind": kind.to_name() }),
+    });
+}
+
+/// proposal-cancel event is emitted when the admin cancels an in progress proposal and the
+/// bond is refunded.
+pub(crate) fn emit_prop_cancelled(prop_id: u32) {
+    emit_event(EventPayload {
+        event: "proposal-cancel",
+        data: json!({ "prop_id": prop_id }),
+    });
+}
+
+/// proposal-retry event is emitted when a previously failed proposal is executed again.
+pub(crate) fn emit_prop_retry(prop_id: u32) {
+    emit_event(EventPayload {
+        event: "proposal-retry",
+        data: json!({ "prop_id": prop_id }),
+    });
+}
+
 /// spam event is emitted when a proposal is marked as spam, removed and bond is slashed.
 pub(crate) fn emit_spam(prop_id: u32) {
     emit_event(EventPayload {
@@ -26,7 +26,10 @@ impl Contract {
 
         // validate proposals
         match &payload.kind {
-            PropKind::FunctionCall { receiver_id, .. } => {
+            PropKind::FunctionCall {
+                receiver_id,
+                actions,
+            } => {
                 let accounts = self.accounts.get().unwrap();
                 if *receiver_id == accounts.congress_coa
                     || *receiver_id == accounts.congress_hom
@@ -36,6 +39,17 @@ impl Contract {
                     "receiver_id can't be a congress house, use a specific proposal to interact with the congress".to_string(),
                 ));
                 }
+                if actions.len() > MAX_ACTIONS {
+                    return Err(CreatePropError::BadRequest(format!(
+                        "max {} actions allowed",
+                        MAX_ACTIONS
+                    )));
+                }
+                if actions.iter().any(|a| a.deposit.0 > 0) {
+                    return Err(CreatePropError::BadRequest(
+                        "actions with attached deposit are not allowed".to_string(),
+                    ));
+                }
             }
             PropKind::UpdateVoteDuration {
                 pre_vote_duration,
@@ -51,6 +65,23 @@ impl Contract {
                 ));
                 }
             }
+            PropKind::UpdateConsent {
+                simple_consent,
+                super_consent,
+            } => {
+                if !simple_consent.verify() || !super_consent.verify() {
+                    return Err(CreatePropError::BadRequest(
+                        "threshold must be a percentage (0-100%)".to_string(),
+                    ));
+                }
+            }
+            PropKind::UpdateMaxVoters { max_voters } => {
+                if *max_voters == 0 {
+                    return Err(CreatePropError::BadRequest(
+                        "max_voters must be positive".to_string(),
+                    ));
+                }
+            }
             _ => (),
         }
 
@@ -82,8 +113,10 @@ impl Contract {
         };
         if active {
             self.proposals.insert(&self.prop_counter, &prop);
+            self.in_progress_ids.insert(&self.prop_counter);
         } else {
             self.pre_vote_proposals.insert(&self.prop_counter, &prop);
+            self.pre_vote_ids.insert(&self.prop_counter);
         }
 
         prop.proposal_storage = match finalize_storage_check(storage_start, 0, caller) {
@@ -95,6 +128,7 @@ impl Contract {
         } else {
             self.pre_vote_proposals.insert(&self.prop_counter, &prop);
         }
+        self.total_bond_locked += bond;
 
         Ok(self.prop_counter)
     }
@@ -113,8 +147,8 @@ impl Contract {
         let mut p = self.assert_pre_vote_prop(prop_id)?;
         let now = env::block_timestamp_ms();
         if now - p.start > self.pre_vote_duration {
-            self.slash_prop(prop_id, p.bond);
-            self.pre_vote_proposals.remove(&prop_id);
+            self.slash_prop(prop_id, &p, p.bond);
+            self.remove_pre_vote_prop(prop_id)?;
             return Ok(false);
         }
         if locked_until <= p.start + self.pre_vote_duration {
@@ -123,7 +157,7 @@ impl Contract {
 
         p.add_support(caller)?;
         if p.support >= self.pre_vote_support {
-            self.pre_vote_proposals.remove(&prop_id);
+            self.remove_pre_vote_prop(prop_id)?;
             self.insert_prop_to_active(prop_id, &mut p);
         } else {
             self.pre_vote_proposals.insert(&prop_id, &p);
@@ -4,9 +4,9 @@ use common::finalize_storage_check;
 use events::*;
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
-    collections::{LazyOption, LookupMap},
+    collections::{LazyOption, LookupMap, UnorderedSet, Vector},
     env::{self, panic_str},
-    json_types::U128,
+    json_types::{U128, U64},
     near_bindgen, require,
     store::LookupSet,
     AccountId, Balance, FunctionError, Gas, PanicOnDefault, Promise, PromiseOrValue, PromiseResult,
@@ -36,6 +36,8 @@ pub struct Contract {
     pub prop_counter: u32,
     /// Set of proposals in the pre-vote queue.
     pub pre_vote_proposals: LookupMap<u32, Proposal>,
+    /// IDs of proposals in the pre-vote queue.
+    pub pre_vote_ids: UnorderedSet<u32>,
     /// Set of active proposals.
     pub proposals: LookupMap<u32, Proposal>,
     /// map (prop_id, voter) -> VoteRecord
@@ -62,6 +64,32 @@ pub struct Contract {
     /// As we don't have a way to remove people from the blacklist, we can add them to the whitelist
     /// and allow them to vote directly.
     pub iom_whitelist: LookupSet<AccountId>,
+
+    /// Compact summaries of finalized proposals removed from the active queue.
+    pub archived_proposals: LookupMap<u32, ArchivedProposal>,
+    /// Records of refunded and slashed proposal bonds. This is the only source of the bond
+    /// status: bonds without a record are locked.
+    pub bond_outcomes: LookupMap<u32, BondOutcome>,
+    /// Gas attached to the `on_execute` callback of the `FunctionCall` proposal execution.
+    pub execute_callback_gas: Gas,
+    /// Sum of the proposal bonds and additional bonds (top ups) which are not refunded nor
+    /// slashed yet.
+    pub total_bond_locked: Balance,
+    /// IDs of successfully executed proposals.
+    pub executed_ids: UnorderedSet<u32>,
+    /// IDs of proposals which execution failed (and were not successfully re-executed yet).
+    pub failed_ids: UnorderedSet<u32>,
+    /// map prop_id -> list of voters, in the order of their first vote.
+    pub proposal_voters: LookupMap<u32, Vector<AccountId>>,
+    /// IDs of proposals in the active queue with the stored `InProgress` status. Proposals
+    /// past the voting period stay in the set until their status is finalized.
+    pub in_progress_ids: UnorderedSet<u32>,
+    /// IDs of executed proposals waiting for the `on_execute` callback.
+    pub pending_callbacks: LookupSet<u32>,
+    /// Upper bound of the number of accounts which can vote, updated only through the
+    /// `UpdateMaxVoters` proposal. Used to check if the proposal consent is still reachable.
+    /// Defaults to `u32::MAX`: the consent is always reachable.
+    pub max_voters: u32,
 }
 
 #[near_bindgen]
@@ -69,6 +97,8 @@ impl Contract {
     #[init]
     /// All duration arguments are in milliseconds.
     /// * hook_auth : map of accounts authorized to call hooks.
+    /// * execute_callback_gas : gas for the `on_execute` callback, defaults to
+    ///   `EXECUTE_CALLBACK_GAS`. Must not exceed `MAX_EXECUTE_CALLBACK_GAS`.
     pub fn new(
         pre_vote_duration: u64,
         vote_duration: u64,
@@ -78,14 +108,18 @@ impl Contract {
         accounts: Accounts,
         simple_consent: Consent,
         super_consent: Consent,
+        execute_callback_gas: Option<U64>,
     ) -> Self {
         require!(
             simple_consent.verify() && super_consent.verify(),
             "threshold must be a percentage (0-100%)"
         );
+        let execute_callback_gas = execute_callback_gas.map_or(EXECUTE_CALLBACK_GAS, |g| Gas(g.0));
+        Self::assert_execute_callback_gas(execute_callback_gas);
         Self {
             prop_counter: 0,
             pre_vote_proposals: LookupMap::new(StorageKey::PreVoteProposals),
+            pre_vote_ids: UnorderedSet::new(StorageKey::PreVoteIds),
             proposals: LookupMap::new(StorageKey::Proposals),
             votes: LookupMap::new(StorageKey::Votes),
             pre_vote_duration,
@@ -97,6 +131,16 @@ impl Contract {
             simple_consent,
             super_consent,
             iom_whitelist: LookupSet::new(StorageKey::IomWhitelist),
+            archived_proposals: LookupMap::new(StorageKey::ArchivedProposals),
+            bond_outcomes: LookupMap::new(StorageKey::BondOutcomes),
+            execute_callback_gas,
+            total_bond_locked: 0,
+            executed_ids: UnorderedSet::new(StorageKey::ExecutedIds),
+            failed_ids: UnorderedSet::new(StorageKey::FailedIds),
+            proposal_voters: LookupMap::new(StorageKey::ProposalVoters),
+            in_progress_ids: UnorderedSet::new(StorageKey::InProgressIds),
+            pending_callbacks: LookupSet::new(StorageKey::PendingCallbacks),
+            max_voters: u32::MAX,
         }
     }
 
@@ -145,13 +189,42 @@ impl Contract {
             return Err(PrevoteError::NotOverdue);
         }
         Promise::new(env::predecessor_account_id()).transfer(SLASH_REWARD);
-        self.slash_prop(id, p.bond - SLASH_REWARD);
+        self.slash_prop(id, &p, p.bond - SLASH_REWARD);
         // NOTE: we don't need to check p.additional_bond: if it is set then the prop wouldn't
         // be in the pre-vote queue.
 
         Ok(())
     }
 
+    /// Allows the proposer to cancel their pre-vote proposal. The proposal is removed and the
+    /// bond is refunded to the proposer.
+    /// Emits `proposal-prevote-cancelled` event.
+    /// Fails if the caller is not the proposer, the proposal is not in the pre-vote queue or
+    /// the proposal is overdue (it can only be slashed, see `slash_prevote_proposal`).
+    #[handle_result]
+    pub fn cancel_prevote_proposal(&mut self, id: u32) -> Result<(), PrevoteError> {
+        let p = match self.assert_pre_vote_prop(id) {
+            Ok(p) => p,
+            Err(_) if self.proposals.contains_key(&id) => return Err(PrevoteError::AlreadyActive),
+            Err(err) => return Err(err),
+        };
+        if p.proposer != env::predecessor_account_id() {
+            return Err(PrevoteError::NotProposer);
+        }
+        if env::block_timestamp_ms() - p.start > self.pre_vote_duration {
+            return Err(PrevoteError::Overdue);
+        }
+        self.remove_pre_vote_prop(id)?;
+        self.bond_outcomes
+            .insert(&id, &p.bond_outcome(BondStatus::Refunded));
+        self.unlock_bond(&p);
+        // the proposal is removed, so we refund the whole bond (including the proposal storage).
+        // NOTE: pre-vote proposals don't have an additional bond.
+        Promise::new(p.proposer).transfer(p.bond);
+        emit_prevote_prop_cancelled(id, p.bond);
+        Ok(())
+    }
+
     #[payable]
     #[handle_result]
     /// Allows to add more bond to a proposal to move it to the active queue. Anyone can top up.
@@ -173,11 +246,13 @@ impl Contract {
             // Note: user wanted to advance the proposal, rather than slash it, so reward is not
             // distributed.
             Promise::new(user.clone()).transfer(bond);
-            self.slash_prop(id, p.bond);
+            self.slash_prop(id, &p, p.bond);
             return Ok(false);
         }
 
-        let required_bond = self.active_queue_bond - p.bond;
+        // `active_queue_bond` could be lowered (by the `UpdateBonds` proposal) below the
+        // proposal bond. In such case the proposal bond is already sufficient.
+        let required_bond = self.active_queue_bond.saturating_sub(p.bond);
         if bond < required_bond {
             return Err(PrevoteError::MinBond);
         }
@@ -186,7 +261,10 @@ impl Contract {
             Promise::new(user.clone()).transfer(diff);
             bond -= diff;
         }
-        p.additional_bond = Some((user, bond));
+        if bond > 0 {
+            p.additional_bond = Some((user, bond));
+            self.total_bond_locked += bond;
+        }
         self.insert_prop_to_active(id, &mut p);
         Ok(true)
     }
@@ -258,7 +336,7 @@ impl Contract {
 
         let mut p = self.remove_pre_vote_prop(prop_id)?;
         if env::block_timestamp_ms() - p.start > self.pre_vote_duration {
-            self.slash_prop(prop_id, p.bond);
+            self.slash_prop(prop_id, &p, p.bond);
             return Ok(false);
         }
         self.insert_prop_to_active(prop_id, &mut p);
@@ -311,11 +389,16 @@ impl Contract {
             return Err(ExecError::AlreadyFinalized);
         }
 
+        let retry = prop.status == ProposalStatus::Failed;
         prop.recompute_status(self.vote_duration, self.prop_consent(&prop));
+        if prop.status != ProposalStatus::InProgress {
+            self.in_progress_ids.remove(&id);
+        }
         match prop.status {
             ProposalStatus::PreVote => panic_str("pre-vote proposal can't be in the active queue"),
             ProposalStatus::InProgress => return Err(ExecError::InProgress),
             ProposalStatus::Executed => return Ok(PromiseOrValue::Value(ExecResponse::Executed)),
+            ProposalStatus::Cancelled => return Err(ExecError::AlreadyFinalized),
             ProposalStatus::Rejected => {
                 self.proposals.insert(&id, &prop);
                 return Ok(PromiseOrValue::Value(ExecResponse::Rejected));
@@ -323,16 +406,37 @@ impl Contract {
             ProposalStatus::Spam => {
                 emit_spam(id);
                 emit_prop_slashed(id, prop.bond); // needs to be called before we zero prop.bond
+                self.bond_outcomes
+                    .insert(&id, &prop.bond_outcome(BondStatus::Slashed));
+                self.unlock_bond(&prop);
                 prop.slash_bond(self.accounts.get().unwrap().community_treasury);
                 self.proposals.remove(&id);
                 return Ok(PromiseOrValue::Value(ExecResponse::Slashed));
             }
             ProposalStatus::Approved | ProposalStatus::Failed => (), // execute below
         };
+        if let PropKind::FunctionCall { actions, .. } = &prop.kind {
+            let required_gas = EXECUTE_GAS.0
+                + self.execute_callback_gas.0
+                + actions.iter().map(|a| a.gas.0).sum::<u64>();
+            require!(
+                env::prepaid_gas().0 >= required_gas,
+                format!("not enough gas, min: {}", required_gas)
+            );
+        }
+        if retry {
+            emit_prop_retry(id);
+        }
 
-        prop.refund_bond();
+        let outcome = prop.bond_outcome(BondStatus::Refunded);
+        self.unlock_bond(&prop);
+        if prop.refund_bond() {
+            self.bond_outcomes.insert(&id, &outcome);
+        }
         prop.status = ProposalStatus::Executed;
         prop.executed_at = Some(env::block_timestamp_ms());
+        self.executed_ids.insert(&id);
+        self.failed_ids.remove(&id);
         let mut out = PromiseOrValue::Value(ExecResponse::Executed);
         match &prop.kind {
             PropKind::Dismiss { dao, member } => {
@@ -376,18 +480,31 @@ impl Contract {
                 self.pre_vote_duration = *pre_vote_duration;
                 self.vote_duration = *vote_duration;
             }
+            PropKind::UpdateConsent {
+                simple_consent,
+                super_consent,
+            } => {
+                self.simple_consent = simple_consent.clone();
+                self.super_consent = super_consent.clone();
+            }
+            PropKind::UpdateMaxVoters { max_voters } => {
+                self.max_voters = *max_voters;
+            }
         };
 
         self.proposals.insert(&id, &prop);
 
         let out = match out {
-            PromiseOrValue::Promise(promise) => promise
-                .then(
-                    ext_self::ext(env::current_account_id())
-                        .with_static_gas(EXECUTE_CALLBACK_GAS)
-                        .on_execute(id),
-                )
-                .into(),
+            PromiseOrValue::Promise(promise) => {
+                self.pending_callbacks.insert(id);
+                promise
+                    .then(
+                        ext_self::ext(env::current_account_id())
+                            .with_static_gas(self.execute_callback_gas)
+                            .on_execute(id),
+                    )
+                    .into()
+            }
             _ => {
                 emit_executed(id);
                 out
@@ -407,6 +524,82 @@ impl Contract {
         self.super_consent = super_consent;
     }
 
+    /// Allows admin to archive a finalized (`Executed` or `Rejected`) proposal to free storage.
+    /// The proposal and its votes are removed and only a compact summary is kept,
+    /// see `get_archived_proposal`. Proposals waiting for the execution callback can't be
+    /// archived.
+    /// NOTE: votes of proposals voted before the contract migration are not removed: the list
+    /// of their voters is not known.
+    pub fn admin_archive_proposal(&mut self, id: u32) {
+        self.assert_admin();
+        let prop = self.proposals.get(&id).expect("proposal not found");
+        require!(
+            matches!(
+                prop.status,
+                ProposalStatus::Executed | ProposalStatus::Rejected
+            ),
+            "only executed or rejected proposals can be archived"
+        );
+        require!(
+            !self.pending_callbacks.contains(&id),
+            "proposal execution is not finished"
+        );
+        self.proposals.remove(&id);
+        self.executed_ids.remove(&id);
+        self.failed_ids.remove(&id);
+        if let Some(mut voters) = self.proposal_voters.remove(&id) {
+            for voter in voters.iter() {
+                self.votes.remove(&(id, voter));
+            }
+            voters.clear();
+        }
+        self.archived_proposals.insert(&id, &prop.to_archived());
+    }
+
+    /// Allows admin to cancel an in progress proposal which consent became unreachable
+    /// assuming at most `max_voters` can vote (see `is_consent_reachable`).
+    /// The proposal bond (and additional bond) is refunded and the proposal status is set to
+    /// `Cancelled`.
+    /// Emits `proposal-cancel` event.
+    pub fn admin_cancel_proposal(&mut self, id: u32) {
+        self.assert_admin();
+        let mut prop = self.proposals.get(&id).expect("proposal not found");
+        prop.recompute_status(self.vote_duration, self.prop_consent(&prop));
+        require!(
+            prop.status == ProposalStatus::InProgress,
+            "only in progress proposals can be cancelled"
+        );
+        require!(
+            !self.is_consent_reachable(id),
+            "proposal consent is still reachable"
+        );
+        let outcome = prop.bond_outcome(BondStatus::Refunded);
+        self.unlock_bond(&prop);
+        if prop.refund_bond() {
+            self.bond_outcomes.insert(&id, &outcome);
+        }
+        prop.status = ProposalStatus::Cancelled;
+        self.proposals.insert(&id, &prop);
+        self.in_progress_ids.remove(&id);
+        emit_prop_cancelled(id);
+    }
+
+    /// Allows admin to recompute the status of an in progress proposal after the voting
+    /// window, so views show the correct status when nobody executes the proposal.
+    /// Only the `Rejected` status is stored: approved and spam proposals must be finalized with
+    /// `execute`, which also executes the proposal or slashes the bond.
+    /// Returns the recomputed status.
+    pub fn admin_recompute_status(&mut self, id: u32) -> ProposalStatus {
+        self.assert_admin();
+        let mut prop = self.proposals.get(&id).expect("proposal not found");
+        prop.recompute_status(self.vote_duration, self.prop_consent(&prop));
+        if prop.status == ProposalStatus::Rejected {
+            self.proposals.insert(&id, &prop);
+            self.in_progress_ids.remove(&id);
+        }
+        prop.status
+    }
+
     /// Allows admin to add a user to the whitelist.
     pub fn admin_add_to_whitelist(&mut self, user: AccountId) {
         self.assert_admin();
@@ -419,6 +612,38 @@ impl Contract {
         self.iom_whitelist.remove(&user);
     }
 
+    /// Allows admin to update the gas attached to the `on_execute` callback. The new value
+    /// must not exceed `MAX_EXECUTE_CALLBACK_GAS`.
+    pub fn admin_set_execute_callback_gas(&mut self, gas: U64) {
+        self.assert_admin();
+        let gas = Gas(gas.0);
+        Self::assert_execute_callback_gas(gas);
+        self.execute_callback_gas = gas;
+    }
+
+    /// Allows admin to quickly update the `pre_vote_support` (amount of support required to
+    /// move a proposal to the active queue). Support must be at least 1.
+    pub fn admin_set_pre_vote_support(&mut self, support: u32) {
+        self.assert_admin();
+        require!(support >= 1, "pre vote support must be at least 1");
+        self.pre_vote_support = support;
+    }
+
+    /// Allows admin to transfer NEAR sent to the contract by mistake. The transferred amount is
+    /// capped by the free balance: account balance minus the storage cost (with 10B margin)
+    /// and the bonds locked in the pre-vote and active proposals (`total_bond_locked`).
+    /// NOTE: bonds of proposals created before the contract migration are not included in
+    /// `total_bond_locked`.
+    /// Returns the transferred amount.
+    pub fn admin_sweep(&mut self, amount: U128, to: AccountId) -> U128 {
+        self.assert_admin();
+        let amount = amount.0.min(self.free_balance());
+        if amount > 0 {
+            Promise::new(to).transfer(amount);
+        }
+        U128(amount)
+    }
+
     // /// udpate voting time for e2e tests purposes
     // /// TODO: remove
     // pub fn admin_update_durations(&mut self, pre_vote_duration: u64, vote_duration: u64) {
@@ -443,15 +668,18 @@ impl Contract {
             1,
             "ERR_UNEXPECTED_CALLBACK_PROMISES"
         );
+        self.pending_callbacks.remove(&prop_id);
         match env::promise_result(0) {
             PromiseResult::NotReady => unreachable!(),
-            PromiseResult::Successful(_) => (),
+            PromiseResult::Successful(_) => emit_executed(prop_id),
             PromiseResult::Failed => {
                 let mut prop = self.proposals.get(&prop_id).expect("proposal not found");
                 prop.status = ProposalStatus::Failed;
                 prop.executed_at = None;
                 self.proposals.insert(&prop_id, &prop);
-                emit_executed(prop_id);
+                self.executed_ids.remove(&prop_id);
+                self.failed_ids.insert(&prop_id);
+                emit_execute_failed(prop_id, &prop.kind);
             }
         };
     }
@@ -460,6 +688,14 @@ impl Contract {
      * INTERNAL
      ****************/
 
+    /// Returns the account balance which is not used for storage nor locked in proposal bonds.
+    fn free_balance(&self) -> Balance {
+        // we leave 10B extra storage
+        let locked =
+            (env::storage_usage() + 10) as u128 * env::storage_byte_cost() + self.total_bond_locked;
+        env::account_balance().saturating_sub(locked)
+    }
+
     fn assert_admin(&self) {
         require!(
             env::predecessor_account_id() == self.accounts.get().unwrap().admin,
@@ -467,14 +703,27 @@ impl Contract {
         );
     }
 
+    fn assert_execute_callback_gas(gas: Gas) {
+        require!(
+            gas <= MAX_EXECUTE_CALLBACK_GAS,
+            format!(
+                "execute callback gas must be at most {}",
+                MAX_EXECUTE_CALLBACK_GAS.0
+            )
+        );
+    }
+
     fn assert_whitelist(&self, account_id: &AccountId) {
         require!(self.iom_whitelist.contains(account_id), "not whitelisted");
     }
 
     fn remove_pre_vote_prop(&mut self, id: u32) -> Result<Proposal, PrevoteError> {
-        self.pre_vote_proposals
+        let p = self
+            .pre_vote_proposals
             .remove(&id)
-            .ok_or(PrevoteError::NotFound)
+            .ok_or(PrevoteError::NotFound)?;
+        self.pre_vote_ids.remove(&id);
+        Ok(p)
     }
 
     fn assert_pre_vote_prop(&mut self, id: u32) -> Result<Proposal, PrevoteError> {
@@ -488,15 +737,28 @@ impl Contract {
         p.status = ProposalStatus::InProgress;
         p.start = env::block_timestamp_ms();
         self.proposals.insert(&prop_id, p);
+        self.in_progress_ids.insert(&prop_id);
         emit_prop_active(prop_id);
     }
 
-    fn slash_prop(&mut self, prop_id: u32, amount: Balance) {
+    /// Slashes the pre-vote proposal bond: transfers `amount` to the treasury.
+    fn slash_prop(&mut self, prop_id: u32, prop: &Proposal, amount: Balance) {
+        self.bond_outcomes
+            .insert(&prop_id, &prop.bond_outcome(BondStatus::Slashed));
+        self.unlock_bond(prop);
         let treasury = self.accounts.get().unwrap().community_treasury;
         Promise::new(treasury).transfer(amount);
         emit_prevote_prop_slashed(prop_id, amount);
     }
 
+    /// Removes the proposal bond and additional bond from the `total_bond_locked`. Must be
+    /// called before the bond is refunded or slashed.
+    // saturating: bonds of proposals created before the migration are not counted.
+    fn unlock_bond(&mut self, prop: &Proposal) {
+        let bond = prop.bond + prop.additional_bond.as_ref().map_or(0, |(_, b)| *b);
+        self.total_bond_locked = self.total_bond_locked.saturating_sub(bond);
+    }
+
     fn prop_consent(&self, prop: &Proposal) -> Consent {
         match prop.kind.required_consent() {
             ConsentKind::Simple => self.simple_consent.clone(),
@@ -505,6 +767,15 @@ impl Contract {
     }
 
     fn add_vote(&mut self, prop_id: u32, user: AccountId, vote: Vote, prop: &mut Proposal) {
+        // proposals voted before the migration don't have the voters list, so we don't start
+        // it for them: the list would be incomplete.
+        let voters = match self.proposal_voters.get(&prop_id) {
+            Some(voters) => Some(voters),
+            None if prop.approve + prop.reject + prop.abstain + prop.spam == 0 => {
+                Some(Vector::new(StorageKey::ProposalVotersList(prop_id)))
+            }
+            None => None,
+        };
         match vote {
             Vote::Abstain => prop.abstain += 1,
             Vote::Approve => prop.approve += 1,
@@ -516,22 +787,32 @@ impl Contract {
             timestamp: env::block_timestamp_ms(),
             vote,
         };
-        if let Some(old_vote) = self.votes.insert(&(prop_id, user), &v) {
+        if let Some(old_vote) = self.votes.insert(&(prop_id, user.clone()), &v) {
             match old_vote.vote {
                 Vote::Approve => prop.approve -= 1,
                 Vote::Reject => prop.reject -= 1,
                 Vote::Abstain => prop.abstain -= 1,
                 Vote::Spam => prop.spam -= 1,
             }
+        } else if let Some(mut voters) = voters {
+            voters.push(&user);
+            self.proposal_voters.insert(&prop_id, &voters);
         }
     }
 }
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod unit_tests {
-    use near_sdk::{test_utils::VMContextBuilder, testing_env, AccountId, VMContext, ONE_NEAR};
+    use near_sdk::{
+        json_types::{Base64VecU8, U64},
+        test_utils::{get_logs, VMContextBuilder},
+        testing_env, AccountId, VMContext, ONE_NEAR,
+    };
 
-    use crate::{view::ConfigOutput, *};
+    use crate::{
+        view::{ConfigOutput, ProposalOutput, ResultSummary, Timeline},
+        *,
+    };
 
     /// 1ms in nano seconds
     const MSECOND: u64 = 1_000_000;
@@ -617,6 +898,7 @@ mod unit_tests {
                 quorum: 5,
                 threshold: 60,
             },
+            None,
         );
         context.block_timestamp = START;
         context.predecessor_account_id = iah_registry();
@@ -721,6 +1003,35 @@ mod unit_tests {
         id
     }
 
+    #[test]
+    fn top_up_lowered_active_queue_bond() {
+        let (mut ctx, mut ctr, id) = setup_ctr(PRE_BOND);
+        let id2 = create_proposal(ctx.clone(), &mut ctr, PRE_BOND);
+        // active_queue_bond lowered below the pre-vote proposal bond
+        ctr.active_queue_bond = PRE_BOND - 1;
+
+        ctx.attached_deposit = BOND;
+        ctx.predecessor_account_id = acc(2);
+        testing_env!(ctx.clone());
+        assert_eq!(Ok(true), ctr.top_up_proposal(id));
+        // the whole deposit is returned
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, acc(2));
+        let p = ctr.get_proposal(id).unwrap().proposal;
+        assert_eq!(p.status, ProposalStatus::InProgress);
+        assert_eq!(p.additional_bond, None);
+
+        // top up without deposit
+        ctx.attached_deposit = 0;
+        testing_env!(ctx);
+        assert_eq!(Ok(true), ctr.top_up_proposal(id2));
+        assert!(get_created_receipts().is_empty());
+        let p = ctr.get_proposal(id2).unwrap().proposal;
+        assert_eq!(p.status, ProposalStatus::InProgress);
+        assert_eq!(p.additional_bond, None);
+    }
+
     #[test]
     fn basic_flows() {
         let (mut ctx, mut ctr, id) = setup_ctr(PRE_BOND);
@@ -1010,6 +1321,310 @@ mod unit_tests {
         }
     }
 
+    /// Creates an approved FunctionCall proposal with 2 actions (10 TGas each) and moves the
+    /// time past the voting period.
+    fn approved_function_call_prop(ctx: &mut VMContext, ctr: &mut Contract) -> u32 {
+        ctx.predecessor_account_id = iah_registry();
+        ctx.attached_deposit = BOND;
+        testing_env!(ctx.clone());
+        let action = ActionCall {
+            method_name: "foo".to_string(),
+            args: Base64VecU8(vec![]),
+            deposit: U128(0),
+            gas: U64(10_000_000_000_000),
+        };
+        let id = ctr
+            .create_proposal(
+                acc(1),
+                iah_proof(),
+                create_prop_payload(
+                    PropKind::FunctionCall {
+                        receiver_id: acc(10),
+                        actions: vec![action.clone(), action],
+                    },
+                    "Proposal unit test".to_string(),
+                ),
+            )
+            .unwrap();
+        vote(
+            ctx.clone(),
+            ctr,
+            vec![acc(1), acc(2), acc(3)],
+            id,
+            Vote::Approve,
+        );
+        ctx.block_timestamp = START + (ctr.vote_duration + 1) * MSECOND;
+        id
+    }
+
+    #[test]
+    #[should_panic(expected = "not enough gas, min: 32000000000000")]
+    fn execute_function_call_not_enough_gas() {
+        let (mut ctx, mut ctr, _) = setup_ctr(BOND);
+        let id = approved_function_call_prop(&mut ctx, &mut ctr);
+        // 8 TGas (execute) + 4 TGas (callback) + 2 * 10 TGas (actions) - 1
+        ctx.prepaid_gas = Gas(32 * Gas::ONE_TERA.0 - 1);
+        testing_env!(ctx);
+        let _ = ctr.execute(id);
+    }
+
+    #[test]
+    fn execute_function_call_min_gas() {
+        let (mut ctx, mut ctr, _) = setup_ctr(BOND);
+        let id = approved_function_call_prop(&mut ctx, &mut ctr);
+        ctx.prepaid_gas = Gas(32 * Gas::ONE_TERA.0);
+        testing_env!(ctx);
+        assert!(matches!(ctr.execute(id), Ok(PromiseOrValue::Promise(_))));
+    }
+
+    #[test]
+    fn execute_function_call_custom_callback_gas() {
+        let (mut ctx, mut ctr, _) = setup_ctr(BOND);
+        assert_eq!(ctr.execute_callback_gas, EXECUTE_CALLBACK_GAS);
+        let id = approved_function_call_prop(&mut ctx, &mut ctr);
+
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx.clone());
+        ctr.admin_set_execute_callback_gas(U64(10 * Gas::ONE_TERA.0));
+        assert_eq!(ctr.execute_callback_gas, Gas(10 * Gas::ONE_TERA.0));
+        assert_eq!(ctr.config().execute_callback_gas, U64(10 * Gas::ONE_TERA.0));
+
+        // 8 TGas (execute) + 10 TGas (callback) + 2 * 10 TGas (actions)
+        ctx.prepaid_gas = Gas(38 * Gas::ONE_TERA.0);
+        testing_env!(ctx);
+        assert!(matches!(ctr.execute(id), Ok(PromiseOrValue::Promise(_))));
+    }
+
+    #[test]
+    #[should_panic(expected = "not enough gas, min: 38000000000000")]
+    fn execute_function_call_custom_callback_gas_not_enough() {
+        let (mut ctx, mut ctr, _) = setup_ctr(BOND);
+        let id = approved_function_call_prop(&mut ctx, &mut ctr);
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx.clone());
+        ctr.admin_set_execute_callback_gas(U64(10 * Gas::ONE_TERA.0));
+
+        ctx.prepaid_gas = Gas(32 * Gas::ONE_TERA.0);
+        testing_env!(ctx);
+        let _ = ctr.execute(id);
+    }
+
+    #[test]
+    #[should_panic(expected = "execute callback gas must be at most 20000000000000")]
+    fn admin_set_execute_callback_gas_too_big() {
+        let (mut ctx, mut ctr, _) = setup_ctr(BOND);
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx);
+        ctr.admin_set_execute_callback_gas(U64(MAX_EXECUTE_CALLBACK_GAS.0 + 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "not authorized")]
+    fn admin_set_execute_callback_gas_not_admin() {
+        let (_, mut ctr, _) = setup_ctr(BOND);
+        ctr.admin_set_execute_callback_gas(U64(10 * Gas::ONE_TERA.0));
+    }
+
+    #[test]
+    fn admin_recompute_status() {
+        let (mut ctx, mut ctr, id) = setup_ctr(BOND);
+        let id2 = create_proposal(ctx.clone(), &mut ctr, BOND);
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2), acc(3)],
+            id2,
+            Vote::Approve,
+        );
+
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx.clone());
+        // voting is still active
+        assert_eq!(ctr.admin_recompute_status(id), ProposalStatus::InProgress);
+
+        ctx.block_timestamp = START + (VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        // no quorum
+        assert_eq!(ctr.admin_recompute_status(id), ProposalStatus::Rejected);
+        assert_eq!(
+            ctr.proposals.get(&id).unwrap().status,
+            ProposalStatus::Rejected
+        );
+        assert!(matches!(ctr.execute(id), Err(ExecError::AlreadyFinalized)));
+
+        // approved proposal is not stored, so it can be executed
+        assert_eq!(ctr.admin_recompute_status(id2), ProposalStatus::Approved);
+        assert_eq!(
+            ctr.proposals.get(&id2).unwrap().status,
+            ProposalStatus::InProgress
+        );
+        assert!(matches!(
+            ctr.execute(id2),
+            Ok(PromiseOrValue::Value(ExecResponse::Executed))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "not authorized")]
+    fn admin_recompute_status_not_admin() {
+        let (_, mut ctr, id) = setup_ctr(BOND);
+        ctr.admin_recompute_status(id);
+    }
+
+    #[test]
+    fn admin_set_pre_vote_support() {
+        let (mut ctx, mut ctr, _) = setup_ctr(BOND);
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx);
+        ctr.admin_set_pre_vote_support(3);
+        assert_eq!(ctr.pre_vote_support, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "pre vote support must be at least 1")]
+    fn admin_set_pre_vote_support_zero() {
+        let (mut ctx, mut ctr, _) = setup_ctr(BOND);
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx);
+        ctr.admin_set_pre_vote_support(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "not authorized")]
+    fn admin_set_pre_vote_support_not_admin() {
+        let (_, mut ctr, _) = setup_ctr(BOND);
+        ctr.admin_set_pre_vote_support(3);
+    }
+
+    #[test]
+    fn execution_retry_failed() {
+        let (mut ctx, mut ctr, _) = setup_ctr(BOND);
+        ctx.predecessor_account_id = iah_registry();
+        ctx.attached_deposit = BOND;
+        testing_env!(ctx.clone());
+        let id = ctr
+            .create_proposal(
+                acc(1),
+                iah_proof(),
+                create_prop_payload(
+                    PropKind::FunctionCall {
+                        receiver_id: acc(10),
+                        actions: vec![ActionCall {
+                            method_name: "foo".to_string(),
+                            args: Base64VecU8(vec![]),
+                            deposit: U128(0),
+                            gas: U64(10_000_000_000_000),
+                        }],
+                    },
+                    "Proposal unit test".to_string(),
+                ),
+            )
+            .unwrap();
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2), acc(3)],
+            id,
+            Vote::Approve,
+        );
+
+        ctx.block_timestamp = START + (ctr.vote_duration + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert!(matches!(ctr.execute(id), Ok(PromiseOrValue::Promise(_))));
+        assert!(get_logs().is_empty(), "first execution is not a retry");
+        assert!(ctr.pending_callbacks.contains(&id));
+        assert_eq!(ctr.get_executed_proposals(0, 10), vec![id]);
+        assert_eq!(ctr.get_failed_proposals(0, 10), vec![]);
+
+        // simulate a failed function call
+        testing_env!(
+            ctx.clone(),
+            near_sdk::VMConfig::test(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed],
+        );
+        ctr.on_execute(id);
+        assert!(!ctr.pending_callbacks.contains(&id));
+        let logs = get_logs();
+        assert_eq!(logs.len(), 1);
+        let event: serde_json::Value =
+            serde_json::from_str(logs[0].strip_prefix("EVENT_JSON:").unwrap()).unwrap();
+        assert_eq!(event["event"], "execute-failed");
+        assert_eq!(
+            event["data"],
+            serde_json::json!({ "prop_id": id, "kind": "function call" })
+        );
+        let p = ctr.get_proposal(id).unwrap();
+        assert_eq!(p.proposal.status, ProposalStatus::Failed);
+        assert_eq!(p.proposal.executed_at, None);
+        assert_eq!(ctr.get_executed_proposals(0, 10), vec![]);
+        assert_eq!(ctr.get_failed_proposals(0, 10), vec![id]);
+
+        // retry
+        testing_env!(ctx.clone());
+        assert!(matches!(ctr.execute(id), Ok(PromiseOrValue::Promise(_))));
+        let expected = format!(
+            r#"EVENT_JSON:{{"standard":"ndc-congress","version":"1.0.0","event":"proposal-retry","data":{{"prop_id":{}}}}}"#,
+            id
+        );
+        assert_eq!(vec![expected], get_logs());
+        let p = ctr.get_proposal(id).unwrap();
+        assert_eq!(p.proposal.status, ProposalStatus::Executed);
+        assert_eq!(ctr.get_executed_proposals(0, 10), vec![id]);
+        assert_eq!(ctr.get_failed_proposals(0, 10), vec![]);
+
+        // successful function call
+        testing_env!(
+            ctx,
+            near_sdk::VMConfig::test(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(vec![])],
+        );
+        ctr.on_execute(id);
+        let expected = format!(
+            r#"EVENT_JSON:{{"standard":"ndc-congress","version":"1.0.0","event":"execute","data":{{"prop_id":{}}}}}"#,
+            id
+        );
+        assert_eq!(vec![expected], get_logs());
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::Executed
+        );
+    }
+
+    #[test]
+    fn get_executed_proposals() {
+        let (mut ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let ids: Vec<u32> = (0..3)
+            .map(|_| create_proposal(ctx.clone(), &mut ctr, BOND))
+            .collect();
+        let id_rejected = create_proposal(ctx.clone(), &mut ctr, BOND);
+        for id in &ids {
+            vote(
+                ctx.clone(),
+                &mut ctr,
+                vec![acc(1), acc(2), acc(3)],
+                *id,
+                Vote::Approve,
+            );
+        }
+        assert_eq!(ctr.get_executed_proposals(0, 10), vec![]);
+
+        ctx.block_timestamp += (VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx);
+        for id in ids.iter().chain([id_rejected].iter()) {
+            ctr.execute(*id).unwrap();
+        }
+        let mut executed = ctr.get_executed_proposals(0, 10);
+        executed.sort();
+        assert_eq!(executed, ids);
+        assert_eq!(ctr.get_executed_proposals(1, 1).len(), 1);
+        assert_eq!(ctr.get_executed_proposals(3, 10), vec![]);
+        assert_eq!(ctr.get_failed_proposals(0, 10), vec![]);
+    }
+
     #[test]
     fn execution_update_bonds() {
         let (mut ctx, mut ctr, _) = setup_ctr(PRE_BOND);
@@ -1093,23 +1708,382 @@ mod unit_tests {
     }
 
     #[test]
-    fn refund_bond_test() {
-        let (mut ctx, mut ctr, id) = setup_ctr(BOND);
-        vote(
-            ctx.clone(),
-            &mut ctr,
-            vec![acc(1), acc(2), acc(3)],
-            id,
-            Vote::Approve,
-        );
-        ctx.block_timestamp = START + (ctr.vote_duration + 1) * MSECOND;
+    fn execution_update_consent() {
+        let (mut ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        ctx.attached_deposit = BOND;
         testing_env!(ctx.clone());
+        let simple_consent = Consent {
+            quorum: 4,
+            threshold: 55,
+        };
+        let super_consent = Consent {
+            quorum: 6,
+            threshold: 70,
+        };
 
-        ctr.execute(id).unwrap();
-        let mut p = ctr.get_proposal(id).unwrap();
-        assert_eq!(p.proposal.status, ProposalStatus::Executed);
+        let kind = PropKind::UpdateConsent {
+            simple_consent: Consent {
+                quorum: 4,
+                threshold: 101,
+            },
+            super_consent,
+        };
+        assert_eq!(
+            ctr.create_proposal(
+                acc(1),
+                iah_proof(),
+                create_prop_payload(kind, "invalid consent".to_owned())
+            ),
+            Err(CreatePropError::BadRequest(
+                "threshold must be a percentage (0-100%)".to_owned()
+            ))
+        );
 
-        // try to get refund again
+        let kind = PropKind::UpdateConsent {
+            simple_consent,
+            super_consent,
+        };
+        let id = ctr
+            .create_proposal(
+                acc(1),
+                iah_proof(),
+                create_prop_payload(kind, "updating consent".to_owned()),
+            )
+            .unwrap();
+
+        // super consent is required: quorum 5 and 60% threshold
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2), acc(3), acc(4), acc(5)],
+            id,
+            Vote::Approve,
+        );
+
+        ctx.predecessor_account_id = acc(10);
+        ctx.block_timestamp += ctr.vote_duration * 10 * MSECOND;
+        testing_env!(ctx.clone());
+
+        match ctr.execute(id) {
+            Ok(_) => (),
+            Err(err) => panic!("expected OK, got: {:?}", err),
+        }
+        let p = ctr.get_proposal(id).unwrap();
+        assert_eq!(p.proposal.status, ProposalStatus::Executed);
+        assert_eq!(ctr.simple_consent, simple_consent);
+        assert_eq!(ctr.super_consent, super_consent);
+    }
+
+    #[test]
+    fn execution_update_max_voters() {
+        let (mut ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        ctx.attached_deposit = BOND;
+        testing_env!(ctx.clone());
+        assert_eq!(
+            ctr.create_proposal(
+                acc(1),
+                iah_proof(),
+                create_prop_payload(
+                    PropKind::UpdateMaxVoters { max_voters: 0 },
+                    "invalid max voters".to_owned()
+                )
+            ),
+            Err(CreatePropError::BadRequest(
+                "max_voters must be positive".to_owned()
+            ))
+        );
+
+        let id = ctr
+            .create_proposal(
+                acc(1),
+                iah_proof(),
+                create_prop_payload(
+                    PropKind::UpdateMaxVoters { max_voters: 100 },
+                    "updating max voters".to_owned(),
+                ),
+            )
+            .unwrap();
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2), acc(3)],
+            id,
+            Vote::Approve,
+        );
+
+        ctx.predecessor_account_id = acc(10);
+        ctx.block_timestamp += ctr.vote_duration * 10 * MSECOND;
+        testing_env!(ctx.clone());
+        match ctr.execute(id) {
+            Ok(_) => (),
+            Err(err) => panic!("expected OK, got: {:?}", err),
+        }
+        assert_eq!(ctr.max_voters, 100);
+        assert_eq!(ctr.config().max_voters, 100);
+    }
+
+    #[test]
+    fn admin_archive_proposal() {
+        let (mut ctx, mut ctr, id) = setup_ctr(BOND);
+        let id2 = create_proposal(ctx.clone(), &mut ctr, BOND);
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2), acc(3)],
+            id,
+            Vote::Approve,
+        );
+        ctx.block_timestamp += (VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert!(ctr.execute(id).is_ok());
+        assert!(ctr.execute(id2).is_ok());
+        let p = ctr.get_proposal(id).unwrap().proposal;
+        assert_eq!(p.status, ProposalStatus::Executed);
+        assert_eq!(
+            ctr.get_proposal(id2).unwrap().proposal.status,
+            ProposalStatus::Rejected
+        );
+
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx);
+        ctr.admin_archive_proposal(id);
+        ctr.admin_archive_proposal(id2);
+        assert_eq!(ctr.get_proposal(id), None);
+        assert_eq!(ctr.get_proposal(id2), None);
+        assert_eq!(
+            ctr.get_archived_proposal(id),
+            Some(ArchivedProposal {
+                proposer: acc(1),
+                kind: "text".to_owned(),
+                status: ProposalStatus::Executed,
+                approve: 3,
+                reject: 0,
+                spam: 0,
+                abstain: 0,
+                start: p.start,
+                executed_at: p.executed_at,
+            })
+        );
+        assert_eq!(
+            ctr.get_archived_proposal(id2).unwrap().status,
+            ProposalStatus::Rejected
+        );
+        assert_eq!(ctr.get_archived_proposal(id2 + 1), None);
+        assert_eq!(ctr.get_executed_proposals(0, 10), vec![]);
+        // the bond status is the same as in the bond records
+        assert_eq!(ctr.result_summary(id).unwrap().bond, BondStatus::Refunded);
+        assert_eq!(ctr.bond_outcome(id).unwrap().status, BondStatus::Refunded);
+        assert_eq!(ctr.result_summary(id2).unwrap().bond, BondStatus::Locked);
+        // votes are removed
+        assert_eq!(ctr.get_vote(id, acc(1)), None);
+        assert!(ctr.get_proposal_votes(id, 0, 10).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "proposal execution is not finished")]
+    fn admin_archive_proposal_pending_callback() {
+        let (mut ctx, mut ctr, _) = setup_ctr(BOND);
+        ctx.predecessor_account_id = iah_registry();
+        ctx.attached_deposit = BOND;
+        testing_env!(ctx.clone());
+        let id = ctr
+            .create_proposal(
+                acc(1),
+                iah_proof(),
+                create_prop_payload(
+                    PropKind::FunctionCall {
+                        receiver_id: acc(10),
+                        actions: vec![ActionCall {
+                            method_name: "foo".to_string(),
+                            args: Base64VecU8(vec![]),
+                            deposit: U128(0),
+                            gas: U64(10_000_000_000_000),
+                        }],
+                    },
+                    "Proposal unit test".to_string(),
+                ),
+            )
+            .unwrap();
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2), acc(3)],
+            id,
+            Vote::Approve,
+        );
+        ctx.block_timestamp = START + (ctr.vote_duration + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert!(matches!(ctr.execute(id), Ok(PromiseOrValue::Promise(_))));
+
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx);
+        ctr.admin_archive_proposal(id);
+    }
+
+    #[test]
+    fn get_account_votes_detailed() {
+        let (mut ctx, mut ctr, id) = setup_ctr(BOND);
+        let id2 = create_proposal(ctx.clone(), &mut ctr, BOND);
+        let id3 = create_proposal(ctx.clone(), &mut ctr, BOND);
+        assert!(ctr.get_account_votes_detailed(acc(1), 0, 10).is_empty());
+
+        ctx.block_timestamp += MSECOND;
+        vote(ctx.clone(), &mut ctr, vec![acc(1)], id, Vote::Approve);
+        let t1 = env::block_timestamp_ms();
+        ctx.block_timestamp += 5 * MSECOND;
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2)],
+            id3,
+            Vote::Reject,
+        );
+        let t3 = env::block_timestamp_ms();
+        assert!(t3 > t1);
+
+        assert_eq!(
+            ctr.get_account_votes_detailed(acc(1), 0, 10),
+            vec![(id, Vote::Approve, t1), (id3, Vote::Reject, t3)]
+        );
+        assert_eq!(
+            ctr.get_account_votes_detailed(acc(2), 0, 10),
+            vec![(id3, Vote::Reject, t3)]
+        );
+
+        // overwriting a vote updates the timestamp
+        ctx.block_timestamp += 10 * MSECOND;
+        vote(ctx.clone(), &mut ctr, vec![acc(1)], id, Vote::Abstain);
+        let t1b = env::block_timestamp_ms();
+        assert!(t1b > t3);
+        vote(ctx, &mut ctr, vec![acc(1)], id2, Vote::Spam);
+        assert_eq!(
+            ctr.get_account_votes_detailed(acc(1), 0, 10),
+            vec![
+                (id, Vote::Abstain, t1b),
+                (id2, Vote::Spam, t1b),
+                (id3, Vote::Reject, t3)
+            ]
+        );
+
+        // pagination
+        assert_eq!(
+            ctr.get_account_votes_detailed(acc(1), 0, 2),
+            vec![(id, Vote::Abstain, t1b), (id2, Vote::Spam, t1b)]
+        );
+        assert_eq!(
+            ctr.get_account_votes_detailed(acc(1), 2, 2),
+            vec![(id3, Vote::Reject, t3)]
+        );
+        assert!(ctr.get_account_votes_detailed(acc(2), 0, 2).is_empty());
+        assert!(ctr.get_account_votes_detailed(acc(1), 3, 10).is_empty());
+        assert!(ctr.get_account_votes_detailed(acc(1), 0, 0).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "only executed or rejected proposals can be archived")]
+    fn admin_archive_proposal_in_progress() {
+        let (mut ctx, mut ctr, id) = setup_ctr(BOND);
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx);
+        ctr.admin_archive_proposal(id);
+    }
+
+    #[test]
+    #[should_panic(expected = "not authorized")]
+    fn admin_archive_proposal_not_admin() {
+        let (_, mut ctr, id) = setup_ctr(BOND);
+        ctr.admin_archive_proposal(id);
+    }
+
+    #[test]
+    fn total_bond_locked() {
+        let (mut ctx, mut ctr, id_prevote) = setup_ctr(PRE_BOND);
+        assert_eq!(ctr.total_bond_locked(), U128(PRE_BOND));
+        let id_executed = create_proposal(ctx.clone(), &mut ctr, BOND);
+        let id_spam = create_proposal(ctx.clone(), &mut ctr, BOND);
+        let id_overdue = create_proposal(ctx.clone(), &mut ctr, PRE_BOND);
+        assert_eq!(ctr.total_bond_locked(), U128(2 * PRE_BOND + 2 * BOND));
+
+        // top up: the excess deposit is returned, so only the missing bond is locked
+        ctx.attached_deposit = BOND;
+        ctx.predecessor_account_id = acc(2);
+        testing_env!(ctx.clone());
+        assert_eq!(Ok(true), ctr.top_up_proposal(id_prevote));
+        assert_eq!(ctr.total_bond_locked(), U128(PRE_BOND + 3 * BOND));
+
+        let accs = vec![acc(1), acc(2), acc(3)];
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            accs.clone(),
+            id_executed,
+            Vote::Approve,
+        );
+        vote(ctx.clone(), &mut ctr, accs, id_spam, Vote::Spam);
+
+        ctx.attached_deposit = 0;
+        ctx.block_timestamp += (PRE_VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx);
+        // refund
+        ctr.execute(id_executed).unwrap();
+        assert_eq!(ctr.total_bond_locked(), U128(PRE_BOND + 2 * BOND));
+        // slash
+        ctr.execute(id_spam).unwrap();
+        assert_eq!(ctr.total_bond_locked(), U128(PRE_BOND + BOND));
+        // pre-vote slash
+        assert_eq!(ctr.slash_prevote_proposal(id_overdue), Ok(()));
+        assert_eq!(ctr.total_bond_locked(), U128(BOND));
+    }
+
+    #[test]
+    fn admin_sweep() {
+        let (mut ctx, mut ctr, _) = setup_ctr(BOND);
+        create_proposal(ctx.clone(), &mut ctr, PRE_BOND);
+        ctx.predecessor_account_id = admin();
+        ctx.attached_deposit = 0;
+        ctx.account_balance = 1000 * ONE_NEAR;
+        testing_env!(ctx.clone());
+        let storage = (env::storage_usage() + 10) as u128 * env::storage_byte_cost();
+        let free = 1000 * ONE_NEAR - storage - BOND - PRE_BOND;
+
+        assert_eq!(ctr.admin_sweep(U128(ONE_NEAR), treasury()), U128(ONE_NEAR));
+        testing_env!(ctx.clone());
+        assert_eq!(
+            ctr.admin_sweep(U128(1000 * ONE_NEAR), treasury()),
+            U128(free)
+        );
+
+        // nothing to sweep
+        ctx.account_balance = storage + BOND;
+        testing_env!(ctx);
+        assert_eq!(ctr.admin_sweep(U128(ONE_NEAR), treasury()), U128(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "not authorized")]
+    fn admin_sweep_not_admin() {
+        let (_, mut ctr, _) = setup_ctr(BOND);
+        ctr.admin_sweep(U128(ONE_NEAR), treasury());
+    }
+
+    #[test]
+    fn refund_bond_test() {
+        let (mut ctx, mut ctr, id) = setup_ctr(BOND);
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2), acc(3)],
+            id,
+            Vote::Approve,
+        );
+        ctx.block_timestamp = START + (ctr.vote_duration + 1) * MSECOND;
+        testing_env!(ctx.clone());
+
+        ctr.execute(id).unwrap();
+        let mut p = ctr.get_proposal(id).unwrap();
+        assert_eq!(p.proposal.status, ProposalStatus::Executed);
+
+        // try to get refund again
         assert!(!p.proposal.refund_bond());
 
         // Get refund for proposal with no status update
@@ -1158,6 +2132,8 @@ mod unit_tests {
                 congress_tc: tc(),
                 admin: admin(),
             },
+            execute_callback_gas: U64(EXECUTE_CALLBACK_GAS.0),
+            max_voters: u32::MAX,
         };
         assert_eq!(ctr.config(), expected);
     }
@@ -1321,6 +2297,32 @@ mod unit_tests {
         assert_eq!(ctr.get_proposal(id), None);
     }
 
+    #[test]
+    fn get_supporters() {
+        let (mut ctx, mut ctr, id) = setup_ctr(PRE_BOND);
+        assert_eq!(ctr.get_supporters(id), vec![]);
+        assert_eq!(ctr.get_supporters(id + 10), vec![]);
+
+        let locked = min_prevote_lock(&ctx);
+        for i in [3, 1, 2] {
+            assert_eq!(ctr.support_proposal(acc(i), locked, None, id), Ok(true));
+        }
+        assert_eq!(ctr.get_supporters(id), vec![acc(1), acc(2), acc(3)]);
+
+        // add the missing support to move the proposal to the active queue
+        ctx.block_timestamp = START + 2 * MSECOND;
+        testing_env!(ctx.clone());
+        let locked = min_prevote_lock(&ctx);
+        for i in 4..=PRE_VOTE_SUPPORT {
+            assert_eq!(
+                ctr.support_proposal(acc(i as u8), locked, None, id),
+                Ok(true)
+            );
+        }
+        assert!(ctr.proposals.get(&id).is_some());
+        assert_eq!(ctr.get_supporters(id), vec![]);
+    }
+
     #[test]
     fn update_consent() {
         let (mut ctx, mut ctr, _) = setup_ctr(BOND);
@@ -1455,6 +2457,85 @@ mod unit_tests {
         assert_eq!(ctr.get_proposal(id).unwrap(), prop);
     }
 
+    #[test]
+    fn create_proposal_function_call_max_actions() {
+        let (mut ctx, mut ctr, _) = setup_ctr(BOND);
+        ctx.predecessor_account_id = iah_registry();
+        ctx.attached_deposit = BOND;
+        testing_env!(ctx.clone());
+        let mk_payload = |n: usize| {
+            create_prop_payload(
+                PropKind::FunctionCall {
+                    receiver_id: acc(10),
+                    actions: vec![
+                        ActionCall {
+                            method_name: "foo".to_string(),
+                            args: Base64VecU8(vec![]),
+                            deposit: U128(0),
+                            gas: U64(10_000_000_000_000),
+                        };
+                        n
+                    ],
+                },
+                "Proposal unit test".to_string(),
+            )
+        };
+
+        assert!(ctr
+            .create_proposal(acc(1), iah_proof(), mk_payload(MAX_ACTIONS))
+            .is_ok());
+        match ctr.create_proposal(acc(1), iah_proof(), mk_payload(MAX_ACTIONS + 1)) {
+            Ok(_) => panic!("expected Err(CreatePropError::BadRequest)"),
+            Err(err) => assert_eq!(
+                err,
+                CreatePropError::BadRequest("max 10 actions allowed".to_string())
+            ),
+        }
+    }
+
+    #[test]
+    fn create_proposal_function_call_with_deposit() {
+        let (mut ctx, mut ctr, _) = setup_ctr(BOND);
+        ctx.predecessor_account_id = iah_registry();
+        ctx.attached_deposit = BOND;
+        testing_env!(ctx.clone());
+        let mk_payload = |deposit: u128| {
+            create_prop_payload(
+                PropKind::FunctionCall {
+                    receiver_id: acc(10),
+                    actions: vec![
+                        ActionCall {
+                            method_name: "foo".to_string(),
+                            args: Base64VecU8(vec![]),
+                            deposit: U128(0),
+                            gas: U64(10_000_000_000_000),
+                        },
+                        ActionCall {
+                            method_name: "bar".to_string(),
+                            args: Base64VecU8(vec![]),
+                            deposit: U128(deposit),
+                            gas: U64(10_000_000_000_000),
+                        },
+                    ],
+                },
+                "Proposal unit test".to_string(),
+            )
+        };
+
+        match ctr.create_proposal(acc(1), iah_proof(), mk_payload(1)) {
+            Ok(_) => panic!("expected Err(CreatePropError::BadRequest)"),
+            Err(err) => assert_eq!(
+                err,
+                CreatePropError::BadRequest(
+                    "actions with attached deposit are not allowed".to_string()
+                )
+            ),
+        }
+        assert!(ctr
+            .create_proposal(acc(1), iah_proof(), mk_payload(0))
+            .is_ok());
+    }
+
     #[test]
     fn create_proposal_function_call_to_congress() {
         let (mut ctx, mut ctr, _) = setup_ctr(BOND);
@@ -1492,10 +2573,894 @@ mod unit_tests {
     }
 
     #[test]
-    fn vote_map() {
-        let (ctx, mut ctr, id1) = setup_ctr(BOND);
-        let id2 = create_proposal(ctx.clone(), &mut ctr, BOND);
-        let locked = min_vote_lock(&ctx);
+    fn cancel_prevote_proposal() {
+        let (mut ctx, mut ctr, id) = setup_ctr(PRE_BOND);
+        let id2 = create_proposal(ctx.clone(), &mut ctr, PRE_BOND);
+        assert_eq!(ctr.total_bond_locked().0, 2 * PRE_BOND);
+
+        ctx.predecessor_account_id = acc(1);
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.cancel_prevote_proposal(id), Ok(()));
+        assert_eq!(ctr.get_proposal(id), None);
+        assert_eq!(ctr.number_of_pre_vote_proposals(), 1);
+        assert_eq!(ctr.total_bond_locked().0, PRE_BOND);
+        assert_eq!(ctr.bond_outcome(id).unwrap().status, BondStatus::Refunded);
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, acc(1));
+        let logs = get_logs();
+        assert_eq!(logs.len(), 1);
+        let event: serde_json::Value =
+            serde_json::from_str(logs[0].strip_prefix("EVENT_JSON:").unwrap()).unwrap();
+        assert_eq!(event["event"], "proposal-prevote-cancelled");
+        assert_eq!(
+            event["data"],
+            serde_json::json!({ "prop_id": id, "bond": PRE_BOND.to_string() })
+        );
+
+        // can't cancel again
+        assert_eq!(ctr.cancel_prevote_proposal(id), Err(PrevoteError::NotFound));
+        assert_eq!(ctr.number_of_pre_vote_proposals(), 1);
+        assert!(ctr.get_proposal(id2).is_some());
+    }
+
+    #[test]
+    fn cancel_prevote_proposal_not_proposer() {
+        let (mut ctx, mut ctr, id) = setup_ctr(PRE_BOND);
+        ctx.predecessor_account_id = acc(2);
+        testing_env!(ctx);
+        assert_eq!(
+            ctr.cancel_prevote_proposal(id),
+            Err(PrevoteError::NotProposer)
+        );
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::PreVote
+        );
+    }
+
+    #[test]
+    fn cancel_prevote_proposal_overdue() {
+        let (mut ctx, mut ctr, id) = setup_ctr(PRE_BOND);
+        ctx.predecessor_account_id = acc(1);
+        ctx.block_timestamp += (PRE_VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(ctr.cancel_prevote_proposal(id), Err(PrevoteError::Overdue));
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::PreVote
+        );
+        // the overdue proposal can still be slashed
+        assert_eq!(ctr.slash_prevote_proposal(id), Ok(()));
+        assert_eq!(ctr.bond_outcome(id).unwrap().status, BondStatus::Slashed);
+    }
+
+    #[test]
+    fn cancel_prevote_proposal_active() {
+        let (mut ctx, mut ctr, id) = setup_ctr(BOND);
+        ctx.predecessor_account_id = acc(1);
+        testing_env!(ctx);
+        assert_eq!(
+            ctr.cancel_prevote_proposal(id),
+            Err(PrevoteError::AlreadyActive)
+        );
+        assert_eq!(
+            ctr.get_proposal(id).unwrap().proposal.status,
+            ProposalStatus::InProgress
+        );
+    }
+
+    #[test]
+    fn number_of_pre_vote_proposals() {
+        let (mut ctx, mut ctr, id) = setup_ctr(PRE_BOND);
+        assert_eq!(ctr.number_of_pre_vote_proposals(), 1);
+        let id2 = create_proposal(ctx.clone(), &mut ctr, PRE_BOND);
+        // proposals with full bond go directly to the active queue
+        create_proposal(ctx.clone(), &mut ctr, BOND);
+        assert_eq!(ctr.number_of_pre_vote_proposals(), 2);
+
+        // promote the first proposal to the active queue
+        ctx.predecessor_account_id = iah_registry();
+        testing_env!(ctx.clone());
+        let locked = min_prevote_lock(&ctx);
+        for i in 1..=PRE_VOTE_SUPPORT {
+            assert_eq!(
+                ctr.support_proposal(acc(i as u8), locked, None, id),
+                Ok(true)
+            );
+        }
+        assert_eq!(ctr.number_of_pre_vote_proposals(), 1);
+
+        // slash the overdue proposal
+        ctx.block_timestamp += (PRE_VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(ctr.slash_prevote_proposal(id2), Ok(()));
+        assert_eq!(ctr.number_of_pre_vote_proposals(), 0);
+        assert_eq!(ctr.number_of_proposals(), 3);
+    }
+
+    #[test]
+    fn slashable_prevote_proposals() {
+        let (mut ctx, mut ctr, id1) = setup_ctr(PRE_BOND);
+        let id2 = create_proposal(ctx.clone(), &mut ctr, PRE_BOND);
+        // active proposals are not tracked
+        create_proposal(ctx.clone(), &mut ctr, BOND);
+        assert_eq!(ctr.slashable_prevote_proposals(), Vec::<u32>::new());
+
+        ctx.block_timestamp += PRE_VOTE_DURATION / 2 * MSECOND;
+        let id3 = create_proposal(ctx.clone(), &mut ctr, PRE_BOND);
+
+        // at the pre-vote duration boundary proposals are not overdue yet
+        ctx.block_timestamp = START + PRE_VOTE_DURATION * MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.slashable_prevote_proposals(), Vec::<u32>::new());
+
+        ctx.block_timestamp += MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.slashable_prevote_proposals(), vec![id1, id2]);
+
+        assert_eq!(ctr.slash_prevote_proposal(id1), Ok(()));
+        assert_eq!(ctr.slashable_prevote_proposals(), vec![id2]);
+
+        ctx.block_timestamp += PRE_VOTE_DURATION * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(ctr.slashable_prevote_proposals(), vec![id2, id3]);
+    }
+
+    #[test]
+    fn slash_reward_for() {
+        let (mut ctx, mut ctr, id) = setup_ctr(PRE_BOND);
+        let id_active = create_proposal(ctx.clone(), &mut ctr, BOND);
+        assert_eq!(ctr.slash_reward_for(id), None);
+        assert_eq!(ctr.slash_reward_for(id_active), None);
+        assert_eq!(ctr.slash_reward_for(100), None);
+
+        ctx.block_timestamp = START + PRE_VOTE_DURATION * MSECOND;
+        testing_env!(ctx.clone());
+        assert_eq!(ctr.slash_reward_for(id), None);
+
+        ctx.block_timestamp += MSECOND;
+        testing_env!(ctx);
+        assert_eq!(ctr.slash_reward_for(id), Some(U128(SLASH_REWARD)));
+        assert_eq!(ctr.slash_reward_for(id_active), None);
+
+        assert_eq!(ctr.slash_prevote_proposal(id), Ok(()));
+        assert_eq!(ctr.slash_reward_for(id), None);
+    }
+
+    #[test]
+    fn bond_outcome() {
+        let (mut ctx, mut ctr, id_prevote) = setup_ctr(PRE_BOND);
+        let id_overdue = create_proposal(ctx.clone(), &mut ctr, PRE_BOND);
+        let id_executed = create_proposal(ctx.clone(), &mut ctr, BOND);
+        let id_spam = create_proposal(ctx.clone(), &mut ctr, BOND);
+        assert_eq!(ctr.bond_outcome(id_spam + 1), None);
+        assert_eq!(
+            ctr.bond_outcome(id_executed),
+            Some(BondOutcome {
+                bond: U128(BOND),
+                additional_bond: None,
+                status: BondStatus::Locked,
+            })
+        );
+
+        // top up
+        ctx.attached_deposit = BOND;
+        ctx.predecessor_account_id = acc(2);
+        testing_env!(ctx.clone());
+        assert_eq!(Ok(true), ctr.top_up_proposal(id_prevote));
+        assert_eq!(
+            ctr.bond_outcome(id_prevote),
+            Some(BondOutcome {
+                bond: U128(PRE_BOND),
+                additional_bond: Some((acc(2), U128(BOND - PRE_BOND))),
+                status: BondStatus::Locked,
+            })
+        );
+
+        let accs = vec![acc(1), acc(2), acc(3)];
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            accs.clone(),
+            id_prevote,
+            Vote::Approve,
+        );
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            accs.clone(),
+            id_executed,
+            Vote::Approve,
+        );
+        vote(ctx.clone(), &mut ctr, accs, id_spam, Vote::Spam);
+
+        ctx.attached_deposit = 0;
+        // past the vote duration and the pre-vote duration
+        ctx.block_timestamp += (PRE_VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert!(ctr.execute(id_prevote).is_ok());
+        assert!(ctr.execute(id_executed).is_ok());
+        assert!(ctr.execute(id_spam).is_ok());
+        assert_eq!(ctr.slash_prevote_proposal(id_overdue), Ok(()));
+
+        assert_eq!(
+            ctr.bond_outcome(id_prevote),
+            Some(BondOutcome {
+                bond: U128(PRE_BOND),
+                additional_bond: Some((acc(2), U128(BOND - PRE_BOND))),
+                status: BondStatus::Refunded,
+            })
+        );
+        assert_eq!(
+            ctr.bond_outcome(id_executed),
+            Some(BondOutcome {
+                bond: U128(BOND),
+                additional_bond: None,
+                status: BondStatus::Refunded,
+            })
+        );
+        // slashed proposals are removed, but the bond record is kept
+        assert_eq!(ctr.get_proposal(id_spam), None);
+        assert_eq!(
+            ctr.bond_outcome(id_spam),
+            Some(BondOutcome {
+                bond: U128(BOND),
+                additional_bond: None,
+                status: BondStatus::Slashed,
+            })
+        );
+        assert_eq!(
+            ctr.bond_outcome(id_overdue),
+            Some(BondOutcome {
+                bond: U128(PRE_BOND),
+                additional_bond: None,
+                status: BondStatus::Slashed,
+            })
+        );
+    }
+
+    #[test]
+    fn number_of_voters() {
+        let (ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let id = create_proposal(ctx.clone(), &mut ctr, BOND);
+        assert_eq!(ctr.number_of_voters(id), 0);
+        assert_eq!(ctr.number_of_voters(id + 1), 0);
+
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2)],
+            id,
+            Vote::Approve,
+        );
+        vote(ctx.clone(), &mut ctr, vec![acc(3)], id, Vote::Spam);
+        assert_eq!(ctr.number_of_voters(id), 3);
+
+        // overwrite votes
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(3)],
+            id,
+            Vote::Reject,
+        );
+        vote(ctx, &mut ctr, vec![acc(2)], id, Vote::Abstain);
+        assert_eq!(ctr.number_of_voters(id), 3);
+        let p = ctr.get_proposal(id).unwrap().proposal;
+        assert_eq!((p.approve, p.reject, p.spam, p.abstain), (0, 2, 0, 1));
+    }
+
+    #[test]
+    fn proposal_timeline() {
+        let (mut ctx, mut ctr, id_prevote) = setup_ctr(PRE_BOND);
+        let id = create_proposal(ctx.clone(), &mut ctr, BOND);
+        assert_eq!(ctr.proposal_timeline(id + 1), None);
+        assert_eq!(ctr.proposal_timeline(id_prevote), None);
+
+        let start = ctx.block_timestamp / MSECOND;
+        let mut expected = Timeline {
+            start,
+            voting_end: start + VOTE_DURATION,
+            executed_at: None,
+            phase: ProposalStatus::InProgress,
+        };
+        assert_eq!(ctr.proposal_timeline(id), Some(expected));
+
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2), acc(3)],
+            id,
+            Vote::Approve,
+        );
+        ctx.block_timestamp += (VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        expected = Timeline {
+            start,
+            voting_end: start + VOTE_DURATION,
+            executed_at: None,
+            phase: ProposalStatus::Approved,
+        };
+        assert_eq!(ctr.proposal_timeline(id), Some(expected));
+
+        ctr.execute(id).unwrap();
+        expected = Timeline {
+            start,
+            voting_end: start + VOTE_DURATION,
+            executed_at: Some(ctx.block_timestamp / MSECOND),
+            phase: ProposalStatus::Executed,
+        };
+        assert_eq!(ctr.proposal_timeline(id), Some(expected));
+    }
+
+    #[test]
+    fn result_summary() {
+        let (mut ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let id_executed = create_proposal(ctx.clone(), &mut ctr, BOND);
+        let id_rejected = create_proposal(ctx.clone(), &mut ctr, BOND);
+        let id_spam = create_proposal(ctx.clone(), &mut ctr, BOND);
+        assert_eq!(ctr.result_summary(id_spam + 1), None);
+
+        let accs = vec![acc(1), acc(2), acc(3)];
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            accs.clone(),
+            id_executed,
+            Vote::Approve,
+        );
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            accs.clone(),
+            id_rejected,
+            Vote::Reject,
+        );
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(4)],
+            id_rejected,
+            Vote::Abstain,
+        );
+        vote(ctx.clone(), &mut ctr, accs, id_spam, Vote::Spam);
+
+        let in_progress = ctr.result_summary(id_executed).unwrap();
+        assert_eq!(in_progress.status, ProposalStatus::InProgress);
+        assert_eq!(in_progress.bond, BondStatus::Locked);
+
+        ctx.block_timestamp += (VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert!(ctr.execute(id_executed).is_ok());
+        assert_eq!(
+            ctr.result_summary(id_executed),
+            Some(ResultSummary {
+                status: ProposalStatus::Executed,
+                approve: 3,
+                reject: 0,
+                abstain: 0,
+                spam: 0,
+                bond: BondStatus::Refunded,
+                executed_at: Some(ctx.block_timestamp / MSECOND),
+            })
+        );
+        assert_eq!(
+            ctr.result_summary(id_rejected),
+            Some(ResultSummary {
+                status: ProposalStatus::Rejected,
+                approve: 0,
+                reject: 3,
+                abstain: 1,
+                spam: 0,
+                bond: BondStatus::Locked,
+                executed_at: None,
+            })
+        );
+        assert_eq!(
+            ctr.result_summary(id_spam),
+            Some(ResultSummary {
+                status: ProposalStatus::Spam,
+                approve: 0,
+                reject: 0,
+                abstain: 0,
+                spam: 3,
+                // the bond is slashed only when the spam proposal is executed
+                bond: BondStatus::Locked,
+                executed_at: None,
+            })
+        );
+
+        // slashed proposals are removed
+        assert!(ctr.execute(id_spam).is_ok());
+        assert_eq!(ctr.result_summary(id_spam), None);
+    }
+
+    #[test]
+    fn simulate_vote() {
+        let (ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let id = create_proposal(ctx.clone(), &mut ctr, BOND);
+        assert_eq!(ctr.simulate_vote(id + 1, Vote::Approve), None);
+        // quorum is not reached
+        assert_eq!(
+            ctr.simulate_vote(id, Vote::Approve),
+            Some(ProposalStatus::Rejected)
+        );
+
+        vote(ctx.clone(), &mut ctr, vec![acc(1)], id, Vote::Approve);
+        vote(ctx.clone(), &mut ctr, vec![acc(2)], id, Vote::Reject);
+        // deciding vote
+        assert_eq!(
+            ctr.simulate_vote(id, Vote::Approve),
+            Some(ProposalStatus::Approved)
+        );
+        assert_eq!(
+            ctr.simulate_vote(id, Vote::Reject),
+            Some(ProposalStatus::Rejected)
+        );
+        assert_eq!(
+            ctr.simulate_vote(id, Vote::Abstain),
+            Some(ProposalStatus::Rejected)
+        );
+
+        // state is not modified
+        let p = ctr.get_proposal(id).unwrap();
+        assert_eq!(p.proposal.approve, 1);
+        assert_eq!(p.proposal.reject, 1);
+        assert_eq!(p.proposal.abstain, 0);
+        assert_eq!(p.proposal.status, ProposalStatus::InProgress);
+
+        // proposal is not in progress
+        let id = create_proposal_with_status(ctx, &mut ctr, ProposalStatus::Executed);
+        assert_eq!(ctr.simulate_vote(id, Vote::Approve), None);
+    }
+
+    #[test]
+    fn votes_to_consent() {
+        let (ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let id = create_proposal(ctx.clone(), &mut ctr, BOND);
+        assert_eq!(ctr.votes_to_consent(id + 1), None);
+        // simple consent: quorum = 3, threshold = 50%
+        assert_eq!(ctr.votes_to_consent(id), Some(3));
+
+        vote(ctx.clone(), &mut ctr, vec![acc(1)], id, Vote::Approve);
+        vote(ctx.clone(), &mut ctr, vec![acc(2)], id, Vote::Reject);
+        assert_eq!(ctr.votes_to_consent(id), Some(1));
+
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(3), acc(4)],
+            id,
+            Vote::Reject,
+        );
+        // 4 approve > 7 * 50%, but 3 approve is not > 6 * 50%
+        assert_eq!(ctr.votes_to_consent(id), Some(3));
+
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(5), acc(6)],
+            id,
+            Vote::Approve,
+        );
+        assert_eq!(ctr.votes_to_consent(id), Some(1));
+        vote(ctx.clone(), &mut ctr, vec![acc(7)], id, Vote::Approve);
+        assert_eq!(ctr.votes_to_consent(id), Some(0));
+        assert_eq!(
+            ctr.simulate_vote(id, Vote::Abstain),
+            Some(ProposalStatus::Approved)
+        );
+
+        // proposal is not in progress
+        let id = create_proposal_with_status(ctx, &mut ctr, ProposalStatus::Executed);
+        assert_eq!(ctr.votes_to_consent(id), None);
+    }
+
+    #[test]
+    fn votes_to_consent_threshold() {
+        let (ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let id = create_proposal(ctx, &mut ctr, BOND);
+        let mut p = ctr.proposals.get(&id).unwrap();
+        p.approve = 2;
+        p.reject = 1;
+        let consent = |threshold| Consent {
+            quorum: 0,
+            threshold,
+        };
+        assert_eq!(p.votes_to_consent(&consent(100)), None);
+        assert_eq!(p.votes_to_consent(&consent(0)), Some(0));
+        assert_eq!(p.votes_to_consent(&consent(60)), Some(0));
+        // 3 approve is not > 4 * 75%, 4 approve > 5 * 75%
+        assert_eq!(p.votes_to_consent(&consent(75)), Some(2));
+        // approve can't be bigger than 100% of qualified votes
+        p.reject = 0;
+        assert_eq!(p.votes_to_consent(&consent(100)), None);
+    }
+
+    #[test]
+    fn is_consent_reachable() {
+        let (mut ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let id = create_proposal(ctx.clone(), &mut ctr, BOND);
+        assert!(!ctr.is_consent_reachable(id + 1));
+        // by default the number of voters is not bounded
+        assert!(ctr.is_consent_reachable(id));
+        // simple consent: quorum = 3, threshold = 50%
+        ctr.max_voters = 3;
+        assert!(ctr.is_consent_reachable(id));
+        ctr.max_voters = 2;
+        assert!(!ctr.is_consent_reachable(id));
+
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2)],
+            id,
+            Vote::Reject,
+        );
+        // 3 more approvals are needed: 3 approve > 5 * 50%
+        ctr.max_voters = 5;
+        assert!(ctr.is_consent_reachable(id));
+        ctr.max_voters = 4;
+        assert!(!ctr.is_consent_reachable(id));
+
+        // admin raises the quorum above the number of possible voters
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx.clone());
+        let consent = Consent {
+            quorum: 10,
+            threshold: 50,
+        };
+        ctr.admin_update_consent(consent.clone(), consent);
+        ctr.max_voters = 9;
+        assert!(!ctr.is_consent_reachable(id));
+        ctr.max_voters = 10;
+        assert!(ctr.is_consent_reachable(id));
+
+        let id = create_proposal_with_status(ctx, &mut ctr, ProposalStatus::Executed);
+        ctr.max_voters = 100;
+        assert!(!ctr.is_consent_reachable(id));
+    }
+
+    #[test]
+    fn admin_cancel_proposal() {
+        let (mut ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let id = create_proposal(ctx.clone(), &mut ctr, BOND);
+        vote(ctx.clone(), &mut ctr, vec![acc(1)], id, Vote::Approve);
+
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx.clone());
+        let consent = Consent {
+            quorum: 10,
+            threshold: 50,
+        };
+        ctr.admin_update_consent(consent.clone(), consent);
+        ctr.max_voters = 5;
+        assert!(!ctr.is_consent_reachable(id));
+
+        ctr.admin_cancel_proposal(id);
+        assert_eq!(
+            get_logs(),
+            vec![format!(
+                r#"EVENT_JSON:{{"standard":"ndc-congress","version":"1.0.0","event":"proposal-cancel","data":{{"prop_id":{}}}}}"#,
+                id
+            )]
+        );
+        let p = ctr.get_proposal(id).unwrap();
+        assert_eq!(p.proposal.status, ProposalStatus::Cancelled);
+        assert_eq!(p.proposal.bond, 0);
+        assert_eq!(
+            ctr.bond_outcome(id),
+            Some(BondOutcome {
+                bond: U128(BOND),
+                additional_bond: None,
+                status: BondStatus::Refunded,
+            })
+        );
+        assert!(!ctr.is_consent_reachable(id));
+        assert!(matches!(ctr.execute(id), Err(ExecError::AlreadyFinalized)));
+
+        // voting is not allowed anymore
+        ctx.predecessor_account_id = iah_registry();
+        ctx.attached_deposit = VOTE_DEPOSIT;
+        testing_env!(ctx.clone());
+        assert_eq!(
+            ctr.vote(
+                acc(2),
+                min_vote_lock(&ctx),
+                None,
+                vote_payload(id, Vote::Approve)
+            ),
+            Err(VoteError::NotInProgress)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "only in progress proposals can be cancelled")]
+    fn admin_cancel_proposal_not_in_progress() {
+        let (mut ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let id = create_proposal_with_status(ctx.clone(), &mut ctr, ProposalStatus::Executed);
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx);
+        ctr.admin_cancel_proposal(id);
+    }
+
+    #[test]
+    #[should_panic(expected = "proposal consent is still reachable")]
+    fn admin_cancel_proposal_consent_reachable() {
+        let (mut ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let id = create_proposal(ctx.clone(), &mut ctr, BOND);
+        vote(ctx.clone(), &mut ctr, vec![acc(1)], id, Vote::Approve);
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx);
+        // simple consent quorum is 3: two more approvals are enough
+        ctr.max_voters = 3;
+        assert!(ctr.is_consent_reachable(id));
+        ctr.admin_cancel_proposal(id);
+    }
+
+    #[test]
+    #[should_panic(expected = "proposal consent is still reachable")]
+    fn admin_cancel_proposal_max_voters_not_set() {
+        let (mut ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        // fresh proposal without votes: the admin can't assume there are no more voters
+        let id = create_proposal(ctx.clone(), &mut ctr, BOND);
+        ctx.predecessor_account_id = admin();
+        testing_env!(ctx);
+        ctr.admin_cancel_proposal(id);
+    }
+
+    #[test]
+    #[should_panic(expected = "not authorized")]
+    fn admin_cancel_proposal_not_admin() {
+        let (ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let id = create_proposal(ctx, &mut ctr, BOND);
+        ctr.admin_cancel_proposal(id);
+    }
+
+    #[test]
+    fn status_counts() {
+        let (mut ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let id_approved = create_proposal(ctx.clone(), &mut ctr, BOND);
+        create_proposal(ctx.clone(), &mut ctr, BOND);
+        create_proposal_with_status(ctx.clone(), &mut ctr, ProposalStatus::Executed);
+        create_proposal_with_status(ctx.clone(), &mut ctr, ProposalStatus::Failed);
+        create_proposal_with_status(ctx.clone(), &mut ctr, ProposalStatus::Cancelled);
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2), acc(3)],
+            id_approved,
+            Vote::Approve,
+        );
+
+        // pre-vote proposals are not counted
+        assert_eq!(
+            ctr.status_counts(0, 10),
+            vec![
+                (ProposalStatus::InProgress, 2),
+                (ProposalStatus::Approved, 0),
+                (ProposalStatus::Rejected, 0),
+                (ProposalStatus::Spam, 0),
+                (ProposalStatus::Executed, 1),
+                (ProposalStatus::Failed, 1),
+                (ProposalStatus::Cancelled, 1),
+            ]
+        );
+
+        // fast forward past the voting period: statuses must be recomputed
+        ctx.block_timestamp += (VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx);
+        assert_eq!(
+            ctr.status_counts(0, 10),
+            vec![
+                (ProposalStatus::InProgress, 0),
+                (ProposalStatus::Approved, 1),
+                (ProposalStatus::Rejected, 1),
+                (ProposalStatus::Spam, 0),
+                (ProposalStatus::Executed, 1),
+                (ProposalStatus::Failed, 1),
+                (ProposalStatus::Cancelled, 1),
+            ]
+        );
+
+        // paginated: the first proposal is a pre-vote one
+        assert_eq!(
+            ctr.status_counts(2, 2),
+            vec![
+                (ProposalStatus::InProgress, 0),
+                (ProposalStatus::Approved, 1),
+                (ProposalStatus::Rejected, 1),
+                (ProposalStatus::Spam, 0),
+                (ProposalStatus::Executed, 0),
+                (ProposalStatus::Failed, 0),
+                (ProposalStatus::Cancelled, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn get_proposals_by_status() {
+        let (mut ctx, mut ctr, id_pre) = setup_ctr(PRE_BOND);
+        let id_approved = create_proposal(ctx.clone(), &mut ctr, BOND);
+        let id_rejected = create_proposal(ctx.clone(), &mut ctr, BOND);
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2), acc(3)],
+            id_approved,
+            Vote::Approve,
+        );
+
+        let ids = |props: Vec<ProposalOutput>| props.iter().map(|p| p.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(ctr.get_proposals_by_status(ProposalStatus::PreVote, 0, 10)),
+            vec![id_pre]
+        );
+        assert_eq!(
+            ids(ctr.get_proposals_by_status(ProposalStatus::InProgress, 0, 10)),
+            vec![id_approved, id_rejected]
+        );
+        assert_eq!(
+            ids(ctr.get_proposals_by_status(ProposalStatus::InProgress, 0, 1)),
+            vec![id_approved]
+        );
+        assert_eq!(
+            ids(ctr.get_proposals_by_status(ProposalStatus::InProgress, id_rejected, 10)),
+            vec![id_rejected]
+        );
+
+        // fast forward past the voting period: statuses must be recomputed
+        ctx.block_timestamp += (VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx.clone());
+        assert!(ctr
+            .get_proposals_by_status(ProposalStatus::InProgress, 0, 10)
+            .is_empty());
+        assert_eq!(
+            ids(ctr.get_proposals_by_status(ProposalStatus::Approved, 0, 10)),
+            vec![id_approved]
+        );
+        assert_eq!(
+            ids(ctr.get_proposals_by_status(ProposalStatus::Rejected, 0, 10)),
+            vec![id_rejected]
+        );
+        assert_eq!(
+            ids(ctr.get_proposals_by_status(ProposalStatus::PreVote, 0, 10)),
+            vec![id_pre]
+        );
+
+        ctr.execute(id_approved).unwrap();
+        assert_eq!(
+            ids(ctr.get_proposals_by_status(ProposalStatus::Executed, 0, 10)),
+            vec![id_approved]
+        );
+        assert!(ctr
+            .get_proposals_by_status(ProposalStatus::Approved, 0, 10)
+            .is_empty());
+        assert_eq!(
+            ctr.get_proposals_by_status(ProposalStatus::Rejected, 0, 0),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn super_consent_kinds() {
+        let (_, ctr, _) = setup_ctr(PRE_BOND);
+        assert_eq!(
+            ctr.super_consent_kinds(),
+            vec!["Dissolve", "TextSuper", "UpdateConsent"]
+        );
+        // every kind is sampled exactly once
+        let names: Vec<&str> = PropKind::samples(hom())
+            .iter()
+            .map(|k| k.variant_name())
+            .collect();
+        assert_eq!(names.len(), 11);
+        assert_eq!(
+            names.iter().collect::<std::collections::HashSet<_>>().len(),
+            names.len()
+        );
+        // names match the JSON representation
+        let kind = serde_json::to_value(PropKind::TextSuper).unwrap();
+        assert_eq!(kind, serde_json::json!("TextSuper"));
+        let kind = serde_json::to_value(PropKind::Dissolve { dao: hom() }).unwrap();
+        assert!(kind.get("Dissolve").is_some());
+    }
+
+    #[test]
+    fn budget_proposal() {
+        let (mut ctx, mut ctr, id_text) = setup_ctr(PRE_BOND);
+        ctx.attached_deposit = PRE_BOND;
+        testing_env!(ctx.clone());
+        let kind = PropKind::ApproveBudget {
+            dao: hom(),
+            prop_id: 12,
+        };
+        let id = ctr
+            .create_proposal(
+                acc(1),
+                iah_proof(),
+                create_prop_payload(kind, "budget".to_string()),
+            )
+            .unwrap();
+        assert_eq!(ctr.budget_proposal(id), Some((hom(), 12)));
+        assert_eq!(ctr.budget_proposal(id_text), None);
+        assert_eq!(ctr.budget_proposal(id + 1), None);
+
+        // budget details are included in the proposal view
+        let out = serde_json::to_value(ctr.get_proposal(id).unwrap()).unwrap();
+        assert_eq!(
+            out["kind"],
+            serde_json::json!({"ApproveBudget": {"dao": "hom.near", "prop_id": 12}})
+        );
+
+        // active queue
+        ctx.attached_deposit = BOND;
+        testing_env!(ctx);
+        ctr.top_up_proposal(id).unwrap();
+        assert_eq!(ctr.budget_proposal(id), Some((hom(), 12)));
+    }
+
+    #[test]
+    fn get_proposals_by_ids() {
+        let (ctx, mut ctr, id_prevote) = setup_ctr(PRE_BOND);
+        let id_active = create_proposal(ctx, &mut ctr, BOND);
+        let out = ctr.get_proposals_by_ids(vec![id_active, 100, id_prevote, id_active]);
+        assert_eq!(out.len(), 4);
+        assert_eq!(out[0], ctr.get_proposal(id_active));
+        assert_eq!(
+            out[0].as_ref().unwrap().proposal.status,
+            ProposalStatus::InProgress
+        );
+        assert_eq!(out[1], None);
+        assert_eq!(out[2].as_ref().unwrap().id, id_prevote);
+        assert_eq!(
+            out[2].as_ref().unwrap().proposal.status,
+            ProposalStatus::PreVote
+        );
+        assert_eq!(out[3], out[0]);
+        assert_eq!(ctr.get_proposals_by_ids(vec![]), vec![]);
+    }
+
+    #[test]
+    fn get_proposals_by_activity() {
+        let (mut ctx, mut ctr, _) = setup_ctr(PRE_BOND);
+        let id1 = create_proposal(ctx.clone(), &mut ctr, BOND);
+        let id2 = create_proposal(ctx.clone(), &mut ctr, BOND);
+        let id3 = create_proposal(ctx.clone(), &mut ctr, BOND);
+        create_proposal_with_status(ctx.clone(), &mut ctr, ProposalStatus::Executed);
+        vote(ctx.clone(), &mut ctr, vec![acc(1)], id1, Vote::Approve);
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2)],
+            id3,
+            Vote::Reject,
+        );
+        vote(ctx.clone(), &mut ctr, vec![acc(3)], id3, Vote::Abstain);
+
+        let ids = |props: Vec<ProposalOutput>| props.iter().map(|p| p.id).collect::<Vec<_>>();
+        // pre-vote and not in progress proposals are skipped
+        assert_eq!(ids(ctr.get_proposals_by_activity(10)), vec![id3, id1, id2]);
+        assert_eq!(ids(ctr.get_proposals_by_activity(2)), vec![id3, id1]);
+        assert!(ctr.get_proposals_by_activity(0).is_empty());
+
+        // fast forward past the voting period: proposals are not in progress any more
+        ctx.block_timestamp += (VOTE_DURATION + 1) * MSECOND;
+        testing_env!(ctx);
+        assert!(ctr.get_proposals_by_activity(10).is_empty());
+        // finalized proposals are removed from the in progress set
+        assert_eq!(ctr.in_progress_ids.len(), 4);
+        assert!(ctr.execute(id1).is_ok());
+        assert!(!ctr.in_progress_ids.contains(&id1));
+        assert_eq!(ctr.in_progress_ids.len(), 3);
+    }
+
+    #[test]
+    fn vote_map() {
+        let (ctx, mut ctr, id1) = setup_ctr(BOND);
+        let id2 = create_proposal(ctx.clone(), &mut ctr, BOND);
+        let locked = min_vote_lock(&ctx);
 
         assert_eq!(
             ctr.vote(acc(1), locked, None, vote_payload(id1, Vote::Approve)),
@@ -1517,6 +3482,139 @@ mod unit_tests {
         assert_eq!(get(id2, acc(2)), vote_record(now, Vote::Spam));
     }
 
+    #[test]
+    fn proposal_output_consent() {
+        let (mut ctx, mut ctr, id) = setup_ctr(BOND);
+        ctx.attached_deposit = BOND;
+        testing_env!(ctx.clone());
+        let id_super = ctr
+            .create_proposal(
+                acc(1),
+                iah_proof(),
+                create_prop_payload(PropKind::TextSuper, "super".to_string()),
+            )
+            .unwrap();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+
+        let p = ctr.get_proposal(id).unwrap();
+        assert_eq!(p.current_quorum, 0);
+        assert_eq!(p.required_quorum, 3);
+        assert_eq!(p.approve_rate_bps, 0);
+
+        vote(ctx.clone(), &mut ctr, vec![acc(1)], id, Vote::Approve);
+        vote(ctx.clone(), &mut ctr, vec![acc(2)], id, Vote::Reject);
+        vote(ctx.clone(), &mut ctr, vec![acc(3)], id, Vote::Spam);
+        vote(ctx.clone(), &mut ctr, vec![acc(4)], id, Vote::Abstain);
+        vote(
+            ctx.clone(),
+            &mut ctr,
+            vec![acc(1), acc(2)],
+            id_super,
+            Vote::Approve,
+        );
+        vote(ctx.clone(), &mut ctr, vec![acc(3)], id_super, Vote::Abstain);
+
+        // simple consent, abstain counts only toward the quorum
+        let p = ctr.get_proposal(id).unwrap();
+        assert_eq!(p.current_quorum, 4);
+        assert_eq!(p.required_quorum, 3);
+        assert_eq!(p.approve_rate_bps, 3333);
+
+        // super consent
+        let p = ctr.get_proposal(id_super).unwrap();
+        assert_eq!(p.current_quorum, 3);
+        assert_eq!(p.required_quorum, 5);
+        assert_eq!(p.approve_rate_bps, 10_000);
+        assert_eq!(
+            ctr.get_proposals(0, 10, None)
+                .iter()
+                .map(|p| (p.current_quorum, p.approve_rate_bps))
+                .collect::<Vec<_>>(),
+            vec![(4, 3333), (3, 10_000)]
+        );
+    }
+
+    #[test]
+    fn get_proposal_votes() {
+        let (mut ctx, mut ctr, id) = setup_ctr(BOND);
+        let locked = min_vote_lock(&ctx);
+        assert!(ctr.get_proposal_votes(id, 0, 10).is_empty());
+
+        let t1 = ctx.block_timestamp;
+        for (a, v) in [
+            (acc(1), Vote::Approve),
+            (acc(2), Vote::Reject),
+            (acc(3), Vote::Abstain),
+        ] {
+            ctr.vote(a, locked, None, vote_payload(id, v)).unwrap();
+        }
+        // overwrite keeps the voter position
+        ctx.block_timestamp += 10 * MSECOND;
+        testing_env!(ctx.clone());
+        ctr.vote(acc(2), locked, None, vote_payload(id, Vote::Approve))
+            .unwrap();
+        let t2 = ctx.block_timestamp;
+
+        let all = vec![
+            (acc(1), vote_record(t1, Vote::Approve)),
+            (acc(2), vote_record(t2, Vote::Approve)),
+            (acc(3), vote_record(t1, Vote::Abstain)),
+        ];
+        assert_eq!(ctr.get_proposal_votes(id, 0, 10), all);
+        assert_eq!(ctr.get_proposal_votes(id, 0, 3), all);
+        assert_eq!(ctr.get_proposal_votes(id, 0, 2), all[..2]);
+        assert_eq!(ctr.get_proposal_votes(id, 2, 2), all[2..]);
+        assert!(ctr.get_proposal_votes(id, 3, 1).is_empty());
+        assert!(ctr.get_proposal_votes(id, 0, 0).is_empty());
+        assert!(ctr.get_proposal_votes(id, u64::MAX, u64::MAX).is_empty());
+        assert!(ctr.get_proposal_votes(id + 1, 0, 10).is_empty());
+
+        // proposal voted before the migration (has votes, but no voters list)
+        let id2 = create_proposal(ctx.clone(), &mut ctr, BOND);
+        let mut p = ctr.proposals.get(&id2).unwrap();
+        p.approve = 1;
+        ctr.proposals.insert(&id2, &p);
+        testing_env!(ctx.clone());
+        ctr.vote(
+            acc(1),
+            min_vote_lock(&ctx),
+            None,
+            vote_payload(id2, Vote::Approve),
+        )
+        .unwrap();
+        assert!(ctr.get_proposal_votes(id2, 0, 10).is_empty());
+        assert!(ctr.get_vote(id2, acc(1)).is_some());
+    }
+
+    #[test]
+    fn get_vote() {
+        let (mut ctx, mut ctr, id) = setup_ctr(BOND);
+        let locked = min_vote_lock(&ctx);
+        assert_eq!(ctr.get_vote(id, acc(1)), None);
+
+        ctr.vote(acc(1), locked, None, vote_payload(id, Vote::Approve))
+            .unwrap();
+        assert_eq!(
+            ctr.get_vote(id, acc(1)),
+            Some(vote_record(ctx.block_timestamp, Vote::Approve))
+        );
+
+        // overwritten vote
+        ctx.block_timestamp += 10 * MSECOND;
+        testing_env!(ctx.clone());
+        ctr.vote(acc(1), locked, None, vote_payload(id, Vote::Reject))
+            .unwrap();
+        assert_eq!(
+            ctr.get_vote(id, acc(1)),
+            Some(vote_record(ctx.block_timestamp, Vote::Reject))
+        );
+
+        // missing voter and missing proposal
+        assert_eq!(ctr.get_vote(id, acc(2)), None);
+        assert_eq!(ctr.get_vote(id + 1, acc(1)), None);
+    }
+
     #[test]
     fn check_serialization() {
         assert_eq!(
@@ -1541,5 +3639,19 @@ mod unit_tests {
             serde_json::to_string(&k).unwrap(),
             "{\"Veto\":{\"dao\":\"hom.near\",\"prop_id\":12}}".to_string()
         );
+
+        let k = PropKind::UpdateConsent {
+            simple_consent: Consent {
+                quorum: 4,
+                threshold: 55,
+            },
+            super_consent: Consent {
+                quorum: 6,
+                threshold: 70,
+            },
+        };
+        let json = r#"{"UpdateConsent":{"simple_consent":{"quorum":4,"threshold":55},"super_consent":{"quorum":6,"threshold":70}}}"#;
+        assert_eq!(serde_json::to_string(&k).unwrap(), json);
+        assert_eq!(serde_json::from_str::<PropKind>(json).unwrap(), k);
     }
 }
@@ -26,6 +26,7 @@ pub struct OldState {
     pub pre_vote_duration: u64,
     pub vote_duration: u64,
     pub accounts: LazyOption<Accounts>,
+    pub iom_whitelist: LookupSet<AccountId>,
 }
 
 #[near_bindgen]
@@ -34,9 +35,31 @@ impl Contract {
     #[init(ignore_state)]
     pub fn migrate() -> Self {
         let old_state: OldState = env::state_read().expect("Old state doesn't exist");
+        // new fields: pre_vote_ids, archived_proposals, bond_outcomes,
+        // execute_callback_gas, total_bond_locked, executed_ids, failed_ids, proposal_voters,
+        // in_progress_ids, pending_callbacks, max_voters
+        // NOTE: total_bond_locked starts from 0 (recomputing it requires reading all proposals),
+        // so bonds of the proposals created before the migration are not counted. For the same
+        // reason executed_ids and failed_ids don't include proposals executed before the migration.
+        // Voters are not enumerable in `votes`, so proposal_voters is not rebuilt: proposals
+        // voted before the migration don't have the voters list.
+        let mut pre_vote_ids = UnorderedSet::new(StorageKey::PreVoteIds);
+        let mut in_progress_ids = UnorderedSet::new(StorageKey::InProgressIds);
+        for id in 1..=old_state.prop_counter {
+            if old_state.pre_vote_proposals.contains_key(&id) {
+                pre_vote_ids.insert(&id);
+            } else if old_state
+                .proposals
+                .get(&id)
+                .map_or(false, |p| p.status == ProposalStatus::InProgress)
+            {
+                in_progress_ids.insert(&id);
+            }
+        }
         Self {
             prop_counter: old_state.prop_counter,
             pre_vote_proposals: old_state.pre_vote_proposals,
+            pre_vote_ids,
             proposals: old_state.proposals,
             votes: old_state.votes,
             pre_vote_bond: old_state.pre_vote_bond,
@@ -47,7 +70,17 @@ impl Contract {
             pre_vote_duration: old_state.pre_vote_duration,
             vote_duration: old_state.vote_duration,
             accounts: old_state.accounts,
-            iom_whitelist: LookupSet::new(StorageKey::IomWhitelist),
+            iom_whitelist: old_state.iom_whitelist,
+            archived_proposals: LookupMap::new(StorageKey::ArchivedProposals),
+            bond_outcomes: LookupMap::new(StorageKey::BondOutcomes),
+            execute_callback_gas: EXECUTE_CALLBACK_GAS,
+            total_bond_locked: 0,
+            executed_ids: UnorderedSet::new(StorageKey::ExecutedIds),
+            failed_ids: UnorderedSet::new(StorageKey::FailedIds),
+            proposal_voters: LookupMap::new(StorageKey::ProposalVoters),
+            in_progress_ids,
+            pending_callbacks: LookupSet::new(StorageKey::PendingCallbacks),
+            max_voters: u32::MAX,
         }
     }
 }
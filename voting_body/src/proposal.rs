@@ -65,6 +65,48 @@ pub struct Proposal {
     pub(crate) proposal_storage: u128,
 }
 
+/// Compact summary of a finalized proposal, kept after the proposal is archived.
+#[derive(BorshSerialize, BorshDeserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(all(test, not(target_arch = "wasm32")), derive(Debug, PartialEq))]
+pub struct ArchivedProposal {
+    pub proposer: AccountId,
+    /// Name of the proposal kind.
+    pub kind: String,
+    pub status: ProposalStatus,
+    pub approve: u32,
+    pub reject: u32,
+    pub spam: u32,
+    pub abstain: u32,
+    pub start: u64,
+    pub executed_at: Option<u64>,
+}
+
+/// Fate of the proposal bond.
+#[derive(BorshSerialize, BorshDeserialize, Serialize)]
+#[cfg_attr(all(test, not(target_arch = "wasm32")), derive(Debug, PartialEq))]
+#[serde(crate = "near_sdk::serde")]
+pub enum BondStatus {
+    /// Bond is kept by the contract.
+    Locked,
+    /// Bond was refunded to the proposer when the proposal was executed or cancelled.
+    Refunded,
+    /// Proposal is a spam or it was overdue in the pre-vote queue: the bond is slashed.
+    Slashed,
+}
+
+/// Record of the proposal bond and additional bond.
+#[derive(BorshSerialize, BorshDeserialize, Serialize)]
+#[cfg_attr(all(test, not(target_arch = "wasm32")), derive(Debug, PartialEq))]
+#[serde(crate = "near_sdk::serde")]
+pub struct BondOutcome {
+    /// original bond deposited by the proposer.
+    pub bond: U128,
+    /// bond added by an account to move the proposal to the active queue.
+    pub additional_bond: Option<(AccountId, U128)>,
+    pub status: BondStatus,
+}
+
 impl Proposal {
     pub fn add_support(&mut self, user: AccountId) -> Result<(), PrevoteError> {
         if self.supported.contains(&user) {
@@ -75,6 +117,19 @@ impl Proposal {
         Ok(())
     }
 
+    /// Returns the bond record with the given status. Must be called before the bond is
+    /// refunded or slashed.
+    pub(crate) fn bond_outcome(&self, status: BondStatus) -> BondOutcome {
+        BondOutcome {
+            bond: U128(self.bond),
+            additional_bond: self
+                .additional_bond
+                .as_ref()
+                .map(|(account, amount)| (account.clone(), U128(*amount))),
+            status,
+        }
+    }
+
     pub fn is_active(&self, vote_duration: u64) -> bool {
         env::block_timestamp_ms() <= self.start + vote_duration
     }
@@ -84,23 +139,64 @@ impl Proposal {
         if self.is_active(vote_duration) || self.status != ProposalStatus::InProgress {
             return;
         }
+        self.status = self.tally_status(&consent);
+    }
+
+    /// Returns the final status based on the current votes, as if the voting was over.
+    pub(crate) fn tally_status(&self, consent: &Consent) -> ProposalStatus {
         let total_no = self.reject + self.spam;
         let qualified = self.approve + total_no;
 
         // check if we have quorum
         if qualified + self.abstain < consent.quorum {
-            self.status = ProposalStatus::Rejected;
-            return;
+            return ProposalStatus::Rejected;
         }
 
         if self.approve > qualified * consent.threshold as u32 / 100 {
-            self.status = ProposalStatus::Approved;
+            ProposalStatus::Approved
         } else if self.spam > self.reject
             && total_no >= qualified * (100 - consent.threshold) as u32 / 100
         {
-            self.status = ProposalStatus::Spam;
+            ProposalStatus::Spam
         } else {
-            self.status = ProposalStatus::Rejected;
+            ProposalStatus::Rejected
+        }
+    }
+
+    /// Returns the minimum number of additional approve votes required to reach the consent
+    /// (both quorum and threshold). Returns None if the consent can't be reached by approve
+    /// votes (threshold is 100%).
+    pub(crate) fn votes_to_consent(&self, consent: &Consent) -> Option<u32> {
+        let qualified = self.approve + self.reject + self.spam;
+        let to_quorum = consent.quorum.saturating_sub(qualified + self.abstain);
+        // approve > qualified * threshold / 100 is equivalent to
+        // 100 * approve > qualified * threshold, so with k more approvals we need:
+        // k * (100 - threshold) > qualified * threshold - 100 * approve
+        let threshold = consent.threshold as i64;
+        let gap = qualified as i64 * threshold - 100 * self.approve as i64;
+        let to_threshold = if gap < 0 {
+            0
+        } else if threshold >= 100 {
+            return None;
+        } else {
+            (gap / (100 - threshold) + 1) as u32
+        };
+        Some(std::cmp::max(to_quorum, to_threshold))
+    }
+
+    /// Returns a compact summary of the proposal, without description, kind details and
+    /// supporters.
+    pub fn to_archived(self) -> ArchivedProposal {
+        ArchivedProposal {
+            proposer: self.proposer,
+            kind: self.kind.to_name(),
+            status: self.status,
+            approve: self.approve,
+            reject: self.reject,
+            spam: self.spam,
+            abstain: self.abstain,
+            start: self.start,
+            executed_at: self.executed_at,
         }
     }
 
@@ -184,6 +280,16 @@ pub enum PropKind {
         pre_vote_duration: u64,
         vote_duration: u64,
     },
+    /// Updates both the simple and the super consent. Requires the Super Consent to approve.
+    UpdateConsent {
+        simple_consent: Consent,
+        super_consent: Consent,
+    },
+    /// Updates the upper bound of the number of voters, used to check if the proposal consent
+    /// is still reachable (see `is_consent_reachable`).
+    UpdateMaxVoters {
+        max_voters: u32,
+    },
 }
 
 impl PropKind {
@@ -199,9 +305,85 @@ impl PropKind {
             PropKind::FunctionCall { .. } => "function call".to_string(),
             PropKind::UpdateBonds { .. } => "config: update bonds".to_string(),
             PropKind::UpdateVoteDuration { .. } => "config: update voting duration".to_string(),
+            PropKind::UpdateConsent { .. } => "config: update consent".to_string(),
+            PropKind::UpdateMaxVoters { .. } => "config: update max voters".to_string(),
+        }
+    }
+
+    /// Name of the kind, as used in the JSON representation.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            PropKind::Dismiss { .. } => "Dismiss",
+            PropKind::Dissolve { .. } => "Dissolve",
+            PropKind::Veto { .. } => "Veto",
+            PropKind::ApproveBudget { .. } => "ApproveBudget",
+            PropKind::Text => "Text",
+            PropKind::TextSuper => "TextSuper",
+            PropKind::FunctionCall { .. } => "FunctionCall",
+            PropKind::UpdateBonds { .. } => "UpdateBonds",
+            PropKind::UpdateVoteDuration { .. } => "UpdateVoteDuration",
+            PropKind::UpdateConsent { .. } => "UpdateConsent",
+            PropKind::UpdateMaxVoters { .. } => "UpdateMaxVoters",
         }
     }
 
+    /// Returns a sample of every kind (in the declaration order), with `acc` used for the
+    /// account fields and zero values for other fields. Used to list kinds by their properties
+    /// (eg the required consent).
+    pub(crate) fn samples(acc: AccountId) -> Vec<PropKind> {
+        let mut kinds = vec![PropKind::Dismiss {
+            dao: acc.clone(),
+            member: acc.clone(),
+        }];
+        while let Some(next) = kinds.last().unwrap().next_sample(&acc) {
+            kinds.push(next);
+        }
+        kinds
+    }
+
+    /// Returns a sample of the kind declared after `self`, or None for the last kind.
+    /// The match is exhaustive, so the compiler flags a new kind missing in the samples.
+    fn next_sample(&self, acc: &AccountId) -> Option<PropKind> {
+        let next = match self {
+            PropKind::Dismiss { .. } => PropKind::Dissolve { dao: acc.clone() },
+            PropKind::Dissolve { .. } => PropKind::Veto {
+                dao: acc.clone(),
+                prop_id: 0,
+            },
+            PropKind::Veto { .. } => PropKind::ApproveBudget {
+                dao: acc.clone(),
+                prop_id: 0,
+            },
+            PropKind::ApproveBudget { .. } => PropKind::Text,
+            PropKind::Text => PropKind::TextSuper,
+            PropKind::TextSuper => PropKind::FunctionCall {
+                receiver_id: acc.clone(),
+                actions: vec![],
+            },
+            PropKind::FunctionCall { .. } => PropKind::UpdateBonds {
+                pre_vote_bond: U128(0),
+                active_queue_bond: U128(0),
+            },
+            PropKind::UpdateBonds { .. } => PropKind::UpdateVoteDuration {
+                pre_vote_duration: 0,
+                vote_duration: 0,
+            },
+            PropKind::UpdateVoteDuration { .. } => PropKind::UpdateConsent {
+                simple_consent: Consent {
+                    quorum: 0,
+                    threshold: 0,
+                },
+                super_consent: Consent {
+                    quorum: 0,
+                    threshold: 0,
+                },
+            },
+            PropKind::UpdateConsent { .. } => PropKind::UpdateMaxVoters { max_voters: 0 },
+            PropKind::UpdateMaxVoters { .. } => return None,
+        };
+        Some(next)
+    }
+
     pub fn required_consent(&self) -> ConsentKind {
         match self {
             Self::Dismiss { .. }
@@ -210,8 +392,11 @@ impl PropKind {
             | Self::Text
             | Self::FunctionCall { .. }
             | Self::UpdateBonds { .. }
-            | Self::UpdateVoteDuration { .. } => ConsentKind::Simple,
-            Self::Dissolve { .. } | Self::TextSuper => ConsentKind::Super,
+            | Self::UpdateVoteDuration { .. }
+            | Self::UpdateMaxVoters { .. } => ConsentKind::Simple,
+            Self::Dissolve { .. } | Self::TextSuper | Self::UpdateConsent { .. } => {
+                ConsentKind::Super
+            }
         }
     }
 }
@@ -231,6 +416,9 @@ pub enum ProposalStatus {
     /// If proposal has failed when executing. Allowed to re-finalize again to either expire or
     /// approved.
     Failed,
+    /// Proposal was cancelled by the admin (eg the consent became unreachable). Bond is
+    /// refunded.
+    Cancelled,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Serialize)]
@@ -10,6 +10,15 @@ pub enum StorageKey {
     Accounts,
     Votes,
     IomWhitelist,
+    ArchivedProposals,
+    BondOutcomes,
+    PreVoteIds,
+    ExecutedIds,
+    FailedIds,
+    ProposalVoters,
+    ProposalVotersList(u32),
+    InProgressIds,
+    PendingCallbacks,
 }
 
 /// External account required for the Voting Body.
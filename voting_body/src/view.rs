@@ -17,6 +17,32 @@ pub struct ProposalOutput {
     pub id: u32,
     #[serde(flatten)]
     pub proposal: Proposal,
+    /// Number of votes counted toward the quorum (approve, reject, spam and abstain).
+    pub current_quorum: u32,
+    /// Quorum of the consent required by the proposal kind.
+    pub required_quorum: u32,
+    /// Approve votes share (in basis points) of the approve, reject and spam votes.
+    /// Abstain votes are not counted, same as in the final tally.
+    pub approve_rate_bps: u16,
+}
+
+impl ProposalOutput {
+    /// * `consent`: consent required by the proposal kind.
+    pub(crate) fn new(id: u32, proposal: Proposal, consent: &Consent) -> Self {
+        let qualified = proposal.approve + proposal.reject + proposal.spam;
+        let approve_rate_bps = if qualified == 0 {
+            0
+        } else {
+            (proposal.approve as u64 * 10_000 / qualified as u64) as u16
+        };
+        ProposalOutput {
+            id,
+            current_quorum: qualified + proposal.abstain,
+            required_quorum: consent.quorum,
+            approve_rate_bps,
+            proposal,
+        }
+    }
 }
 
 /// This is format of output via JSON for the config.
@@ -33,6 +59,36 @@ pub struct ConfigOutput {
     pub vote_duration: u64,
     pub pre_vote_duration: u64,
     pub accounts: Accounts,
+    pub execute_callback_gas: U64,
+    pub max_voters: u32,
+}
+
+/// Summary of the proposal result.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(PartialEq, Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct ResultSummary {
+    pub status: ProposalStatus,
+    pub approve: u32,
+    pub reject: u32,
+    pub abstain: u32,
+    pub spam: u32,
+    pub bond: BondStatus,
+    pub executed_at: Option<u64>,
+}
+
+/// Timeline of a proposal from the active queue. All times are in milliseconds.
+#[derive(Serialize)]
+#[cfg_attr(test, derive(PartialEq, Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct Timeline {
+    /// Time when the proposal was moved to the active queue.
+    pub start: u64,
+    /// `start + vote_duration`: the last moment when the proposal can be voted.
+    pub voting_end: u64,
+    pub executed_at: Option<u64>,
+    /// Current phase: the proposal status recomputed at the query time.
+    pub phase: ProposalStatus,
 }
 
 #[near_bindgen]
@@ -81,10 +137,9 @@ impl Contract {
         };
 
         iter.filter_map(|id| {
-            proposals.get(&id).map(|mut proposal| {
-                proposal.recompute_status(self.vote_duration, self.prop_consent(&proposal));
-                ProposalOutput { id, proposal }
-            })
+            proposals
+                .get(&id)
+                .map(|proposal| self.proposal_output(id, proposal))
         })
         .collect()
     }
@@ -95,23 +150,230 @@ impl Contract {
         if p.is_none() {
             p = self.pre_vote_proposals.get(&id);
         }
-        p.map(|mut proposal| {
-            proposal.recompute_status(self.vote_duration, self.prop_consent(&proposal));
-            ProposalOutput { id, proposal }
+        p.map(|proposal| self.proposal_output(id, proposal))
+    }
+
+    /// Returns proposals with the given ids, in the same order, from the active or the
+    /// pre-vote queue. `None` is returned for proposals which don't exist.
+    pub fn get_proposals_by_ids(&self, ids: Vec<u32>) -> Vec<Option<ProposalOutput>> {
+        ids.into_iter().map(|id| self.get_proposal(id)).collect()
+    }
+
+    /// Returns the congress `(dao, prop_id)` referenced by an `ApproveBudget` proposal from
+    /// the pre-vote or the active queue. The budget amount is not part of the proposal: it is
+    /// defined by the referenced congress proposal.
+    /// Returns None if the proposal doesn't exist or is not an `ApproveBudget` proposal.
+    pub fn budget_proposal(&self, id: u32) -> Option<(AccountId, u32)> {
+        let p = self
+            .proposals
+            .get(&id)
+            .or_else(|| self.pre_vote_proposals.get(&id))?;
+        match p.kind {
+            PropKind::ApproveBudget { dao, prop_id } => Some((dao, prop_id)),
+            _ => None,
+        }
+    }
+
+    /// Returns names of the proposal kinds (as used in the JSON `kind` field) which require
+    /// the super consent to be approved.
+    pub fn super_consent_kinds(&self) -> Vec<String> {
+        PropKind::samples(env::current_account_id())
+            .into_iter()
+            .filter(|k| matches!(k.required_consent(), ConsentKind::Super))
+            .map(|k| k.variant_name().to_owned())
+            .collect()
+    }
+
+    /// Returns the proposal result: live status, votes and the bond status. The bond status
+    /// is read from the bond records (see `bond_outcome`): the bond is `Locked` until it is
+    /// refunded or slashed. Archived proposals are included. Returns None if the proposal
+    /// doesn't exist or was slashed (slashed proposals are removed).
+    pub fn result_summary(&self, id: u32) -> Option<ResultSummary> {
+        let bond = self
+            .bond_outcome(id)
+            .map_or(BondStatus::Locked, |o| o.status);
+        if let Some(p) = self.get_proposal(id) {
+            let p = p.proposal;
+            return Some(ResultSummary {
+                bond,
+                status: p.status,
+                approve: p.approve,
+                reject: p.reject,
+                abstain: p.abstain,
+                spam: p.spam,
+                executed_at: p.executed_at,
+            });
+        }
+        self.archived_proposals.get(&id).map(|p| ResultSummary {
+            bond,
+            status: p.status,
+            approve: p.approve,
+            reject: p.reject,
+            abstain: p.abstain,
+            spam: p.spam,
+            executed_at: p.executed_at,
         })
     }
 
+    /// Returns the proposal timeline: voting start and end, execution time and the current
+    /// phase. Returns None if the proposal doesn't exist or is in the pre-vote queue.
+    pub fn proposal_timeline(&self, id: u32) -> Option<Timeline> {
+        self.proposals.get(&id).map(|mut p| {
+            p.recompute_status(self.vote_duration, self.prop_consent(&p));
+            Timeline {
+                start: p.start,
+                voting_end: p.start + self.vote_duration,
+                executed_at: p.executed_at,
+                phase: p.status,
+            }
+        })
+    }
+
+    /// Returns the proposal bond, additional bond and what happened with them. Records of
+    /// refunded and slashed bonds are kept even if the proposal was removed. Returns None
+    /// if the proposal doesn't exist.
+    pub fn bond_outcome(&self, id: u32) -> Option<BondOutcome> {
+        if let Some(outcome) = self.bond_outcomes.get(&id) {
+            return Some(outcome);
+        }
+        self.proposals
+            .get(&id)
+            .or_else(|| self.pre_vote_proposals.get(&id))
+            .map(|p| p.bond_outcome(BondStatus::Locked))
+    }
+
+    /// Returns the compact summary of an archived proposal.
+    pub fn get_archived_proposal(&self, id: u32) -> Option<ArchivedProposal> {
+        self.archived_proposals.get(&id)
+    }
+
     /// * `id`: proposal id
     /// * `voter`: account address.
-    /// Returns none if voter didn't vote for the given proposal id.
+    /// Returns none if voter didn't vote for the given proposal id. If the vote was
+    /// overwritten, the latest vote is returned.
     pub fn get_vote(&self, id: u32, voter: AccountId) -> Option<VoteRecord> {
         self.votes.get(&(id, voter))
     }
 
+    /// Returns votes of the `account` as `(proposal_id, vote, timestamp_ms)` tuples, sorted by
+    /// proposal id. Checks up to `limit` proposals, starting from the `from_index` position
+    /// (proposal IDs start from 1, so `from_index = 0` starts from the first proposal).
+    /// The timestamp is the block time (in milliseconds) of the latest vote for the given
+    /// proposal.
+    pub fn get_account_votes_detailed(
+        &self,
+        account: AccountId,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<(u32, Vote, u64)> {
+        let end = min(from_index.saturating_add(limit), self.prop_counter);
+        (from_index.saturating_add(1)..=end)
+            .filter_map(|id| {
+                self.votes
+                    .get(&(id, account.clone()))
+                    .map(|v| (id, v.vote, v.timestamp))
+            })
+            .collect()
+    }
+
+    /// Returns the number of distinct accounts which voted for the given proposal.
+    /// Overwritten votes are not double counted: the previous vote is removed from the tally.
+    /// Returns 0 if the proposal is not in the active queue.
+    pub fn number_of_voters(&self, id: u32) -> u32 {
+        self.proposals
+            .get(&id)
+            .map_or(0, |p| p.approve + p.reject + p.spam + p.abstain)
+    }
+
     pub fn number_of_proposals(&self) -> u32 {
         self.prop_counter
     }
 
+    /// Returns the sum of the proposal bonds and additional bonds (top ups) which are not
+    /// refunded nor slashed yet.
+    /// NOTE: bonds of proposals created before the contract migration are not included.
+    pub fn total_bond_locked(&self) -> U128 {
+        U128(self.total_bond_locked)
+    }
+
+    /// Returns up to `limit` votes of the proposal as `(voter, vote)` pairs, starting from the
+    /// `from` position. Voters are ordered by their first vote, and the latest vote is
+    /// returned for overwritten votes.
+    /// Returns an empty list for proposals voted before the contract migration.
+    pub fn get_proposal_votes(
+        &self,
+        prop_id: u32,
+        from: u64,
+        limit: u64,
+    ) -> Vec<(AccountId, VoteRecord)> {
+        let voters = match self.proposal_voters.get(&prop_id) {
+            Some(voters) => voters,
+            None => return vec![],
+        };
+        (from..min(from.saturating_add(limit), voters.len()))
+            .filter_map(|i| {
+                let voter = voters.get(i)?;
+                let vote = self.votes.get(&(prop_id, voter.clone()))?;
+                Some((voter, vote))
+            })
+            .collect()
+    }
+
+    /// Returns up to `limit` IDs of executed proposals, starting from the `from_index`
+    /// position. Proposals which execution failed are not included.
+    /// NOTE: IDs are not sorted.
+    pub fn get_executed_proposals(&self, from_index: u64, limit: u64) -> Vec<u32> {
+        self.executed_ids
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Returns up to `limit` IDs of proposals which execution failed (and were not
+    /// successfully re-executed yet), starting from the `from_index` position.
+    /// NOTE: IDs are not sorted.
+    pub fn get_failed_proposals(&self, from_index: u64, limit: u64) -> Vec<u32> {
+        self.failed_ids
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Returns the number of proposals in the pre-vote queue.
+    pub fn number_of_pre_vote_proposals(&self) -> u32 {
+        self.pre_vote_ids.len() as u32
+    }
+
+    /// Returns IDs (in ascending order) of the pre-vote proposals which are overdue and can be
+    /// slashed with `slash_prevote_proposal`.
+    pub fn slashable_prevote_proposals(&self) -> Vec<u32> {
+        let now = env::block_timestamp_ms();
+        let mut ids: Vec<u32> = self
+            .pre_vote_ids
+            .iter()
+            .filter(|id| {
+                self.pre_vote_proposals
+                    .get(id)
+                    .map_or(false, |p| now - p.start > self.pre_vote_duration)
+            })
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Returns the reward (`SLASH_REWARD`) the caller would receive for slashing the pre-vote
+    /// proposal with `slash_prevote_proposal`. Returns None if the proposal is not in the
+    /// pre-vote queue or is not overdue yet.
+    pub fn slash_reward_for(&self, id: u32) -> Option<U128> {
+        let p = self.pre_vote_proposals.get(&id)?;
+        if env::block_timestamp_ms() - p.start <= self.pre_vote_duration {
+            return None;
+        }
+        Some(U128(SLASH_REWARD))
+    }
+
     pub fn config(&self) -> ConfigOutput {
         ConfigOutput {
             prop_counter: self.prop_counter,
@@ -123,6 +385,8 @@ impl Contract {
             pre_vote_duration: self.pre_vote_duration,
             vote_duration: self.vote_duration,
             accounts: self.accounts.get().unwrap(),
+            execute_callback_gas: U64(self.execute_callback_gas.0),
+            max_voters: self.max_voters,
         }
     }
 
@@ -136,7 +400,157 @@ impl Contract {
         self._get_proposals(from_index, limit, reverse, true)
     }
 
+    /// Returns up to `limit` proposals with the given status, starting from the `from_index`
+    /// proposal id. Status of the active proposals is recomputed before filtering.
+    /// Proposals from the pre-vote queue are only returned when `status == PreVote`.
+    pub fn get_proposals_by_status(
+        &self,
+        status: ProposalStatus,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<ProposalOutput> {
+        let proposals = if status == ProposalStatus::PreVote {
+            &self.pre_vote_proposals
+        } else {
+            &self.proposals
+        };
+
+        (max(from_index, 1)..=self.prop_counter)
+            .filter_map(|id| {
+                proposals
+                    .get(&id)
+                    .map(|proposal| self.proposal_output(id, proposal))
+                    .filter(|out| out.proposal.status == status)
+            })
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Returns up to `limit` in progress proposals from the active queue, sorted by the
+    /// number of votes cast (approve, reject, spam and abstain) in descending order.
+    /// Proposals with the same number of votes are sorted by id.
+    pub fn get_proposals_by_activity(&self, limit: u64) -> Vec<ProposalOutput> {
+        let mut props: Vec<ProposalOutput> = self
+            .in_progress_ids
+            .iter()
+            .filter_map(|id| {
+                self.proposals
+                    .get(&id)
+                    .map(|proposal| self.proposal_output(id, proposal))
+                    .filter(|out| out.proposal.status == ProposalStatus::InProgress)
+            })
+            .collect();
+        props.sort_by_key(|p| {
+            let votes =
+                p.proposal.approve + p.proposal.reject + p.proposal.spam + p.proposal.abstain;
+            (std::cmp::Reverse(votes), p.id)
+        });
+        props.truncate(limit as usize);
+        props
+    }
+
+    /// Returns the number of proposals in the active queue per status, counting up to `limit`
+    /// proposal ids starting from the `from_index` id. Status of the active proposals is
+    /// recomputed before counting.
+    pub fn status_counts(&self, from_index: u32, limit: u32) -> Vec<(ProposalStatus, u32)> {
+        let mut counts = vec![
+            (ProposalStatus::InProgress, 0),
+            (ProposalStatus::Approved, 0),
+            (ProposalStatus::Rejected, 0),
+            (ProposalStatus::Spam, 0),
+            (ProposalStatus::Executed, 0),
+            (ProposalStatus::Failed, 0),
+            (ProposalStatus::Cancelled, 0),
+        ];
+        let from_index = max(from_index, 1);
+        let end = min(
+            self.prop_counter,
+            from_index.saturating_add(limit).saturating_sub(1),
+        );
+        for id in from_index..=end {
+            if let Some(mut proposal) = self.proposals.get(&id) {
+                proposal.recompute_status(self.vote_duration, self.prop_consent(&proposal));
+                if let Some((_, count)) = counts.iter_mut().find(|(s, _)| *s == proposal.status) {
+                    *count += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Returns accounts which supported the pre-vote proposal, sorted by account id.
+    /// Returns an empty list if the proposal doesn't exist or was already moved to the active
+    /// queue (the supporters list is cleared when the proposal becomes active).
+    pub fn get_supporters(&self, id: u32) -> Vec<AccountId> {
+        let mut supporters: Vec<AccountId> = self
+            .pre_vote_proposals
+            .get(&id)
+            .map(|p| p.supported.into_iter().collect())
+            .unwrap_or_default();
+        supporters.sort();
+        supporters
+    }
+
+    /// Returns the status the proposal would get if the voting ended right after casting
+    /// the given vote. Doesn't modify the state. Returns None if the proposal doesn't exist
+    /// or is not in progress.
+    /// NOTE: the simulated vote is always added, even if the caller already voted (in which
+    /// case the real vote would overwrite the previous one).
+    pub fn simulate_vote(&self, id: u32, vote: Vote) -> Option<ProposalStatus> {
+        let mut prop = self.proposals.get(&id)?;
+        if prop.status != ProposalStatus::InProgress {
+            return None;
+        }
+        match vote {
+            Vote::Abstain => prop.abstain += 1,
+            Vote::Approve => prop.approve += 1,
+            Vote::Reject => prop.reject += 1,
+            Vote::Spam => prop.spam += 1,
+        };
+        Some(prop.tally_status(&self.prop_consent(&prop)))
+    }
+
+    /// Returns the number of additional approve votes required for the proposal to reach the
+    /// consent (quorum and threshold) if the voting ended now.
+    /// Returns None if the proposal doesn't exist, is not in progress or the consent can't
+    /// be reached by approve votes.
+    pub fn votes_to_consent(&self, id: u32) -> Option<u32> {
+        let prop = self.proposals.get(&id)?;
+        if prop.status != ProposalStatus::InProgress {
+            return None;
+        }
+        prop.votes_to_consent(&self.prop_consent(&prop))
+    }
+
+    /// Returns false if the proposal consent (quorum and threshold) can't be reached anymore,
+    /// even if all remaining voters approve, assuming at most `max_voters` can vote (including
+    /// the votes already cast). `max_voters` is set through the `UpdateMaxVoters` proposal.
+    /// This happens eg when the admin raises the quorum above the number of verified humans.
+    /// Such proposals can be cancelled by the admin.
+    /// Returns false if the proposal doesn't exist or is not in progress.
+    pub fn is_consent_reachable(&self, id: u32) -> bool {
+        let prop = match self.proposals.get(&id) {
+            Some(p) if p.status == ProposalStatus::InProgress => p,
+            _ => return false,
+        };
+        let cast = prop.approve + prop.reject + prop.spam + prop.abstain;
+        prop.votes_to_consent(&self.prop_consent(&prop))
+            .map_or(false, |needed| {
+                needed <= self.max_voters.saturating_sub(cast)
+            })
+    }
+
     pub fn is_iom_whitelisted(&self, account_id: &AccountId) -> bool {
         self.iom_whitelist.contains(&account_id)
     }
 }
+
+impl Contract {
+    /// Recomputes the proposal status and returns the proposal view, including the progress
+    /// toward the consent required by the proposal kind.
+    fn proposal_output(&self, id: u32, mut proposal: Proposal) -> ProposalOutput {
+        let consent = self.prop_consent(&proposal);
+        proposal.recompute_status(self.vote_duration, consent.clone());
+        ProposalOutput::new(id, proposal, &consent)
+    }
+}